@@ -0,0 +1,259 @@
+use praeses_blackjack::blackjack;
+use praeses_blackjack::blackjack::actors::{dealers, players};
+use praeses_blackjack::{cards, cli, Player};
+
+use std::{path::PathBuf, process};
+
+use clap::Parser;
+
+/// Program to play Blackjack
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+struct Args {
+    /// Number of real players in the game
+    #[clap(short = 'h', long, value_parser, default_value_t = 1)]
+    human_players: u32,
+
+    /// If included, will add a bot player to the game.
+    #[clap(short = 'r', long, value_parser, default_value_t = false)]
+    robot_player: bool,
+
+    /// If included, the bot plays perfect basic strategy for the table rules instead of its
+    /// simple rules of thumb.
+    #[clap(long, value_parser, default_value_t = false, requires = "robot-player")]
+    basic_strategy: bool,
+
+    /// Path to a CSV or TOML strategy chart for the bot to play by exactly
+    #[clap(
+        long,
+        value_parser,
+        requires = "robot-player",
+        conflicts_with = "basic-strategy"
+    )]
+    chart: Option<PathBuf>,
+
+    /// If included, the bot counts cards (with the Hi-Lo count unless --counting-system says
+    /// otherwise), betting more when the count is high
+    #[clap(
+        long,
+        value_parser,
+        default_value_t = false,
+        requires = "robot-player",
+        conflicts_with_all = &["basic-strategy", "chart"]
+    )]
+    count_cards: bool,
+
+    /// The counting system the card counting bot uses (hi-lo, ko, hi-opt-1, hi-opt-2, omega-2,
+    /// zen, or wong-halves)
+    #[clap(long, value_parser, requires = "count-cards")]
+    counting_system: Option<blackjack::counting::CountStyle>,
+
+    /// Path to a CSV table of index plays for the card counting bot, instead of the ones for
+    /// its counting system (the Fab 4 and Illustrious 18 for hi-lo, and none for the others)
+    #[clap(long, value_parser, requires = "count-cards")]
+    deviations: Option<PathBuf>,
+
+    /// Initial buy-in for betting (set to 0 to disable betting)
+    #[clap(short = 'b', long, value_parser, default_value_t = 500)]
+    betting_buy_in: u32,
+
+    /// Common set of table rules to play by (vegas-strip, atlantic-city, downtown, or european)
+    #[clap(long, value_parser)]
+    preset: Option<blackjack::RulesPreset>,
+
+    /// Path to a TOML or JSON file with the table rules to play by
+    #[clap(long, value_parser, conflicts_with = "preset")]
+    rules: Option<PathBuf>,
+
+    /// Number of decks to use in the game (overrides the table rules)
+    #[clap(short = 'd', long, value_parser)]
+    num_decks: Option<u32>,
+
+    /// How deep to deal into the shoe before reshuffling, as a percentage like 75% or a number
+    /// of decks like 4.5 (overrides the table rules)
+    #[clap(long, value_parser)]
+    penetration: Option<cards::Penetration>,
+
+    /// Payout ratio for the game (overrides the table rules)
+    #[clap(short, long, value_parser)]
+    payout_ratio: Option<f64>,
+
+    /// If included, the dealer hits on soft 17 (overrides the table rules)
+    #[clap(long, value_parser, default_value_t = false)]
+    hit_soft_17: bool,
+
+    /// When players can surrender half their bet: no-surrender, late, or early (overrides the
+    /// table rules)
+    #[clap(long, value_parser)]
+    surrender: Option<blackjack::SurrenderRule>,
+
+    /// Which hands players can double down on: any-two-cards, nine-to-eleven, ten-to-eleven,
+    /// or never (overrides the table rules)
+    #[clap(long, value_parser)]
+    double_down: Option<blackjack::DoubleDownRule>,
+
+    /// If included, players can't double down after splitting a pair (overrides the table rules)
+    #[clap(long, value_parser, default_value_t = false)]
+    no_double_after_split: bool,
+
+    /// Most hands a player can split into in one round, or 1 to disable splitting (overrides
+    /// the table rules)
+    #[clap(long, value_parser)]
+    max_split_hands: Option<u32>,
+
+    /// If included, split aces can be split again (overrides the table rules)
+    #[clap(long, value_parser, default_value_t = false)]
+    resplit_aces: bool,
+
+    /// If included, split aces can take more than one card (overrides the table rules)
+    #[clap(long, value_parser, default_value_t = false)]
+    hit_split_aces: bool,
+
+    /// If included, players aren't offered insurance (overrides the table rules)
+    #[clap(long, value_parser, default_value_t = false)]
+    no_insurance: bool,
+
+    /// Deal from a continuous shuffling machine, which shuffles the cards back in after every round
+    #[clap(long, value_parser, default_value_t = false)]
+    csm: bool,
+
+    /// The kind of deck the shoe is filled with (standard, or spanish for a deck without tens)
+    #[clap(long, value_parser, default_value = "standard")]
+    deck: cards::deck::DeckStyle,
+
+    /// How the dealer shuffles (uniform, riffle, strip, box, or human)
+    #[clap(long, value_parser, default_value = "uniform")]
+    shuffle: cards::shuffle::ShuffleStyle,
+
+    /// Seed for shuffling, to replay the exact same shoe (shuffles randomly if not given)
+    #[clap(long, value_parser)]
+    seed: Option<u64>,
+}
+
+impl Args {
+    /// Gets the table rules picked on the commandline, from a preset or a rules file.
+    /// Exits with an error if the rules file can't be loaded.
+    fn table_rules(&self) -> blackjack::TableRules {
+        if let Some(path) = &self.rules {
+            return blackjack::TableRules::from_file(path).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+        }
+
+        self.preset
+            .map_or_else(blackjack::TableRules::default, |preset| preset.rules())
+    }
+
+    /// Changes any rules that were set on their own on the commandline.
+    fn override_rules(&self, rules: &mut blackjack::TableRules) {
+        if let Some(num_decks) = self.num_decks {
+            rules.num_decks = num_decks;
+        }
+        if let Some(penetration) = self.penetration {
+            rules.penetration = penetration;
+        }
+        if let Some(payout_ratio) = self.payout_ratio {
+            rules.payout_ratio = payout_ratio;
+        }
+        if self.hit_soft_17 {
+            rules.dealer_hits_soft_17 = true;
+        }
+        if let Some(surrender) = self.surrender {
+            rules.surrender_rule = surrender;
+        }
+        if let Some(double_down) = self.double_down {
+            rules.double_down_rule = double_down;
+        }
+        if self.no_double_after_split {
+            rules.double_after_split = false;
+        }
+        if let Some(max_split_hands) = self.max_split_hands {
+            rules.max_split_hands = max_split_hands;
+        }
+        if self.resplit_aces {
+            rules.resplit_aces = true;
+        }
+        if self.hit_split_aces {
+            rules.hit_split_aces = true;
+        }
+        if self.no_insurance {
+            rules.offer_insurance = false;
+        }
+    }
+}
+
+fn main() {
+    let args = Args::parse();
+
+    let mut rules = args.table_rules();
+    args.override_rules(&mut rules);
+
+    let shuffle_mode = if args.csm {
+        cards::ShuffleMode::Continuous
+    } else {
+        cards::ShuffleMode::CutCard
+    };
+
+    let options = blackjack::GameOptions {
+        betting_buy_in: args.betting_buy_in,
+        rules,
+        shuffle_mode,
+        shuffler: args.shuffle.shuffler(),
+        deck: args.deck.builder(),
+    };
+
+    let mut players: Vec<Box<dyn Player>> = Vec::new();
+    if let Some(path) = &args.chart {
+        let bot =
+            players::ChartPlayer::from_file(options.betting_buy_in, path).unwrap_or_else(|e| {
+                eprintln!("{}", e);
+                process::exit(1);
+            });
+        players.push(Box::new(bot));
+    } else if args.robot_player && args.count_cards {
+        let mut bot = players::CountingPlayer::with_system(
+            options.betting_buy_in,
+            &options.rules,
+            args.counting_system.unwrap_or_default(),
+        )
+        .with_deck(&options.deck);
+        if let Some(path) = &args.deviations {
+            let deviations =
+                blackjack::strategy::DeviationTable::from_file(path).unwrap_or_else(|e| {
+                    eprintln!("{}", e);
+                    process::exit(1);
+                });
+            bot = bot.with_deviations(deviations);
+        }
+        players.push(Box::new(bot));
+    } else if args.robot_player && args.basic_strategy {
+        players.push(Box::new(players::ChartPlayer::basic_strategy(
+            options.betting_buy_in,
+            &options.rules,
+        )));
+    } else if args.robot_player {
+        players.push(Box::new(players::AutoPlayer::new(options.betting_buy_in)));
+    }
+    for _ in 0..args.human_players {
+        players.push(Box::new(cli::HumanPlayer::new(options.betting_buy_in)));
+    }
+
+    let mut terminal = cli::Terminal::new();
+    match args.seed {
+        Some(seed) => {
+            let mut rng = cards::seeded_rng(seed);
+            blackjack::play_blackjack_with_rng::<dealers::StandardDealer, _>(
+                players,
+                &options,
+                &mut terminal,
+                &mut rng,
+            );
+        }
+        None => {
+            blackjack::play_blackjack::<dealers::StandardDealer>(players, &options, &mut terminal);
+        }
+    }
+
+    println!("Thanks for playing!")
+}
@@ -1,32 +1,59 @@
 //! Blackjack game functionality.
 
 pub mod actors;
+pub mod counting;
+pub mod events;
+pub mod rules;
+pub mod scenario;
+pub mod strategy;
+
+pub use events::{Event, EventStream, Frontend, Observer, Participant};
+pub use rules::{DoubleDownRule, HoleCardPolicy, RulesPreset, SurrenderRule, TableRules};
+pub use scenario::Scenario;
 
-use std::cmp;
 use std::cmp::Ordering;
-use std::{fmt, io};
+use std::fmt;
+use std::rc::Rc;
+
+use rand::Rng;
 
 use crate::blackjack::actors::dealers::Dealer;
 use crate::blackjack::actors::players::{self, Player};
+use crate::blackjack::actors::Action;
 use crate::cards;
+use crate::cards::shuffle::{Shuffler, UniformShuffle};
 
 /// Options for running a game of blackjack.
 pub struct GameOptions {
-    /// How many players are at the table
-    pub num_players: u32,
-    /// Whether or not to play alongside a bot player.
-    pub bot_player: bool,
-    /// How many decks are used to create the deck (most popular is six for a 312 card game).
-    pub num_decks: u32,
     /// How much money to give players to start with (and if/when they run out).
     pub betting_buy_in: u32,
-    /// Payout for winning in blackjack, usually 3:2 or 6:5.
-    /// Higher is better for the players, lower is better for the house.
-    pub payout_ratio: f64,
+    /// The rules the table plays by.
+    pub rules: TableRules,
+    /// Whether the dealer shuffles the shoe by hand or uses a continuous shuffling machine.
+    pub shuffle_mode: cards::ShuffleMode,
+    /// How the shoe gets shuffled.
+    pub shuffler: Rc<dyn Shuffler>,
+    /// The deck the shoe is filled with, `rules.num_decks` times over.
+    pub deck: cards::DeckBuilder,
+}
+
+impl Default for GameOptions {
+    /// The same settings the command line uses when none are given: a $500 buy-in at a table
+    /// with the default rules, dealing standard decks from a hand-shuffled shoe that's shuffled
+    /// perfectly randomly.
+    fn default() -> Self {
+        GameOptions {
+            betting_buy_in: 500,
+            rules: TableRules::default(),
+            shuffle_mode: cards::ShuffleMode::CutCard,
+            shuffler: Rc::new(UniformShuffle),
+            deck: cards::DeckBuilder::standard(),
+        }
+    }
 }
 
 /// Possible results for each player each round.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum PlayerRoundResult {
     /// Natural or Blackjack is when the player has 21 in the first two cards. (But if the dealer matches then it's a standoff)
     Natural,
@@ -36,6 +63,10 @@ pub enum PlayerRoundResult {
     Lose,
     /// If their value and the dealer's are exactly equal at the end of the round.
     Standoff,
+    /// If they had a natural and took even money when the dealer showed an ace.
+    EvenMoney,
+    /// If they surrendered their hand, getting back half their bet.
+    Surrender,
 }
 
 impl fmt::Display for PlayerRoundResult {
@@ -46,105 +77,182 @@ impl fmt::Display for PlayerRoundResult {
             PlayerRoundResult::Win => write!(f, "You win! Congratulations!"),
             PlayerRoundResult::Lose => write!(f, "Sorry, you lose."),
             PlayerRoundResult::Standoff => write!(f, "It's a stand-off!"),
+            PlayerRoundResult::EvenMoney => write!(f, "Blackjack! You took even money."),
+            PlayerRoundResult::Surrender => write!(f, "You surrendered."),
         }
     }
 }
 
-type PlayerResult = (Box<dyn Player>, PlayerRoundResult);
+/// How a single round of blackjack played out.
+pub struct RoundOutcome {
+    /// The players once the round was settled, in the order they're seated.
+    pub players: Vec<Box<dyn Player>>,
+    /// The results of each player's hands, in the same order as the players.
+    pub results: Vec<Vec<PlayerRoundResult>>,
+    /// The shoe after the round, with every card from the round in its discard tray.
+    pub shoe: cards::Shoe,
+}
+
+/// How many actions in a row a player can have rejected before their hand stands as it is,
+/// so a bot that keeps asking for the same illegal move can't hold up the table forever.
+const MAX_REJECTED_ACTIONS: u32 = 3;
+
+/// How many cards to keep in the shoe for each hand at the table (the dealer's included), so
+/// there's a round's worth left to deal. Reshuffles come early rather than dealing with fewer.
+const CARDS_PER_HAND: usize = 3;
+
+/// What the game panics with if the shoe runs out before everyone has their first two cards.
+/// The shoe is reshuffled between rounds whenever fewer than a round's worth of cards are
+/// left (however deep the cut card is), so only a shoe that's too small for everyone at the
+/// table, or one stacked too short, can.
+const SHOE_RAN_OUT: &str = "The shoe ran out of cards while dealing the round";
+
+/// A player along with the result of each hand they played in a round.
+type PlayerResult = (Box<dyn Player>, Vec<PlayerRoundResult>);
 
 type RoundResult = Vec<PlayerResult>;
 
 enum IntermediateRoundResult<D: Dealer> {
     Finished {
         results: RoundResult,
-        leftover_deck: cards::Deck,
+        shoe: cards::Shoe,
     },
     Unfinished(InProgressGame<D>),
 }
 
+impl<D: Dealer> IntermediateRoundResult<D> {
+    /// Finishes the round with the given results, clearing the dealer's cards off the table.
+    fn finished(results: RoundResult, mut dealer: D, mut shoe: cards::Shoe) -> Self {
+        dealer.discard_hand(&mut shoe);
+        IntermediateRoundResult::Finished { results, shoe }
+    }
+}
+
 struct ReadyGame<D: Dealer> {
     players: Vec<Box<dyn Player>>,
     dealer: D,
-    deck: cards::Deck,
+    shoe: cards::Shoe,
 }
 
 struct InProgressGame<D: Dealer> {
     players: Vec<Box<dyn Player>>,
     dealer: D,
-    deck: cards::Deck,
+    shoe: cards::Shoe,
 }
 
 impl<D> ReadyGame<D>
 where
     D: Dealer,
 {
-    fn new(options: &GameOptions) -> ReadyGame<D> {
-        let mut players: Vec<Box<dyn players::Player>> = Vec::new();
-
-        if options.bot_player {
-            players.push(Box::new(players::AutoPlayer::new(options.betting_buy_in)));
-        }
-
-        for _ in 0..options.num_players {
-            // Will change with multiplayer and such -- We will have to call new on different players!
-            players.push(Box::new(players::HumanPlayer::new(options.betting_buy_in)));
-        }
-
-        let mut deck = cards::create_multideck(options.num_decks);
-        cards::shuffle_deck(&mut deck);
-
+    fn new<R: Rng + ?Sized>(
+        players: Vec<Box<dyn Player>>,
+        options: &GameOptions,
+        rng: &mut R,
+    ) -> ReadyGame<D> {
         ReadyGame {
             players,
             dealer: D::new(),
-            deck,
+            shoe: cards::Shoe::with_decks(&options.deck, options.rules.num_decks, rng)
+                .with_shuffler(Rc::clone(&options.shuffler))
+                .with_cut_card(options.rules.penetration, options.rules.cut_card_spread)
+                .with_burn_cards(options.rules.burn_cards)
+                .with_shuffle_mode(options.shuffle_mode),
         }
     }
 
-    fn deal_hands(mut self) -> InProgressGame<D> {
-        for player in &mut self.players {
-            player.place_bet();
-        }
+    fn deal_hands(mut self, rules: &TableRules, frontend: &mut dyn Frontend) -> InProgressGame<D> {
+        let event = Event::RoundStarted {
+            cards_seen: self.shoe.cards_dealt(),
+            cards_remaining: self.shoe.len(),
+        };
+        announce(&mut self.players, frontend, &event);
 
-        println!("");
+        for index in 0..self.players.len() {
+            let player = &mut self.players[index];
+            // Players can come to the table with their bet already down, like in scenarios.
+            if player.hands()[0].bet.is_none() {
+                player.place_bet(rules);
+            }
+            if let Some(amount) = player.hands()[0].bet {
+                let event = Event::BetPlaced {
+                    player: player.name().to_string(),
+                    amount,
+                };
+                announce(&mut self.players, frontend, &event);
+            }
+        }
 
         for _ in 0..2 {
-            for player in &mut self.players {
-                player.recieve_card(self.deck.pop().unwrap());
+            for index in 0..self.players.len() {
+                let card = self.shoe.draw().expect(SHOE_RAN_OUT);
+                let player = &mut self.players[index];
+                player.recieve_card(card);
+                let event = Event::CardDealt {
+                    to: Participant::Player(player.name().to_string()),
+                    card: Some(card),
+                };
+                announce(&mut self.players, frontend, &event);
+            }
+            // Without a hole card, the dealer only gets their second card once the players are done.
+            if rules.hole_card_policy == HoleCardPolicy::Peek || self.dealer.hand().is_empty() {
+                let card = self.shoe.draw().expect(SHOE_RAN_OUT);
+                self.dealer.recieve_card(card);
+                // With a hole card, the dealer's first card is dealt face down.
+                let face_down =
+                    rules.hole_card_policy == HoleCardPolicy::Peek && self.dealer.hand().len() == 1;
+                let event = Event::CardDealt {
+                    to: Participant::Dealer,
+                    card: if face_down { None } else { Some(card) },
+                };
+                announce(&mut self.players, frontend, &event);
             }
-            self.dealer.recieve_card(self.deck.pop().unwrap());
         }
 
         InProgressGame {
             players: self.players,
             dealer: self.dealer,
-            deck: self.deck,
+            shoe: self.shoe,
         }
     }
 
     fn from_previous_round(
-        players: Vec<Box<dyn Player>>,
-        leftover_deck: cards::Deck,
+        mut players: Vec<Box<dyn Player>>,
+        mut shoe: cards::Shoe,
         options: &GameOptions,
+        frontend: &mut dyn Frontend,
     ) -> ReadyGame<D> {
-        let mut ready_players: Vec<Box<dyn Player>> = Vec::new();
-        for mut player in players {
-            player.buy_in_if_broke(options.betting_buy_in);
-            ready_players.push(player);
-        }
-
-        let mut deck: cards::Deck;
-        if leftover_deck.len() > reshuffle_number(options.num_decks).try_into().unwrap() {
-            deck = leftover_deck;
-        } else {
-            println!("Reshuffling deck...\n");
-            deck = cards::create_multideck(options.num_decks);
-            cards::shuffle_deck(&mut deck);
-        };
+        for index in 0..players.len() {
+            let player = &mut players[index];
+            if player.buy_in_if_broke(options.betting_buy_in, &options.rules) {
+                let event = Event::PlayerBoughtIn {
+                    player: player.name().to_string(),
+                    amount: options.betting_buy_in,
+                };
+                announce(&mut players, frontend, &event);
+            }
+        }
+
+        // Like in a casino, the round the cut card comes out in gets finished before the reshuffle.
+        let mut reshuffled = shoe.prepare_next_round();
+        // However deep the cut card is, there have to be enough cards left for another round.
+        if !reshuffled && shoe.len() < CARDS_PER_HAND * (players.len() + 1) {
+            shoe.shuffle();
+            reshuffled = true;
+        }
+        if reshuffled {
+            announce(&mut players, frontend, &Event::ShoeReshuffled);
+            if shoe.cards_burned() > 0 {
+                let event = Event::CardsBurned {
+                    count: shoe.cards_burned(),
+                };
+                announce(&mut players, frontend, &event);
+            }
+        }
 
         ReadyGame {
-            players: ready_players,
+            players,
             dealer: D::new(),
-            deck,
+            shoe,
         }
     }
 }
@@ -153,176 +261,406 @@ impl<D> InProgressGame<D>
 where
     D: Dealer,
 {
-    fn handle_naturals(self) -> IntermediateRoundResult<D> {
-        let mut round_results: RoundResult = Vec::new();
-        let dealer_has_natural = hand_is_natural(self.dealer.hand());
+    fn offer_insurance(&mut self, frontend: &mut dyn Frontend) {
+        let event = Event::InsuranceOffered {
+            dealer_upcard: *self.dealer.upcard(),
+        };
+        announce(&mut self.players, frontend, &event);
+        for index in 0..self.players.len() {
+            let player = &mut self.players[index];
+            player.offer_insurance();
 
-        if dealer_has_natural {
-            self.dealer.show_true_hand();
-            println!("Dealer has blackjack!");
-            for player in self.players {
-                player.show_hand();
-                let player_has_natural = hand_is_natural(player.hand());
-                if player_has_natural {
-                    round_results.push((player, PlayerRoundResult::Standoff));
-                } else {
-                    round_results.push((player, PlayerRoundResult::Lose));
-                }
+            let name = player.name().to_string();
+            let hand = &player.hands()[0];
+            let event = if hand.took_even_money {
+                Some(Event::EvenMoneyTaken { player: name })
+            } else {
+                hand.insurance.map(|amount| Event::InsurancePlaced {
+                    player: name,
+                    amount,
+                })
+            };
+            if let Some(event) = event {
+                announce(&mut self.players, frontend, &event);
             }
-            return IntermediateRoundResult::Finished {
-                results: round_results,
-                leftover_deck: self.deck,
+        }
+    }
+
+    fn offer_early_surrender(&mut self, frontend: &mut dyn Frontend) {
+        let dealer_upcard = *self.dealer.upcard();
+        announce(
+            &mut self.players,
+            frontend,
+            &Event::EarlySurrenderOffered { dealer_upcard },
+        );
+        for index in 0..self.players.len() {
+            let player = &mut self.players[index];
+            player.offer_early_surrender(&dealer_upcard);
+            if player.hands()[0].surrendered {
+                let event = Event::ActionTaken {
+                    who: Participant::Player(player.name().to_string()),
+                    action: Action::Surrender,
+                };
+                announce(&mut self.players, frontend, &event);
+            }
+        }
+    }
+
+    /// Turns over the dealer's hole card, if they have one.
+    fn reveal_hole_card(&mut self, rules: &TableRules, frontend: &mut dyn Frontend) {
+        if rules.hole_card_policy == HoleCardPolicy::Peek {
+            let event = Event::HoleCardRevealed {
+                card: self.dealer.hand()[0],
             };
-        } else {
-            let all_players_have_blackjack = &self.players[..]
-                .into_iter()
-                .all(|player| hand_is_natural(player.hand()));
-            if *all_players_have_blackjack {
-                self.dealer.show_true_hand();
-                for player in self.players {
-                    player.show_hand();
-                    round_results.push((player, PlayerRoundResult::Natural));
-                }
-                return IntermediateRoundResult::Finished {
-                    results: round_results,
-                    leftover_deck: self.deck,
+            announce(&mut self.players, frontend, &event);
+        }
+    }
+
+    /// Settles everyone's insurance once it's known whether the dealer has blackjack, and if
+    /// they do, finishes the round with the results for every player.
+    fn check_dealer_natural(
+        mut self,
+        rules: &TableRules,
+        frontend: &mut dyn Frontend,
+    ) -> IntermediateRoundResult<D> {
+        let dealer_has_natural = hand_is_natural(self.dealer.hand());
+        for index in 0..self.players.len() {
+            let player = &mut self.players[index];
+            if let Some(amount) = player.settle_insurance(dealer_has_natural) {
+                let event = Event::InsuranceSettled {
+                    player: player.name().to_string(),
+                    amount,
+                    dealer_has_natural,
+                    money: player.money(),
                 };
+                announce(&mut self.players, frontend, &event);
             }
         }
+
+        if !dealer_has_natural {
+            return IntermediateRoundResult::Unfinished(self);
+        }
+
+        self.reveal_hole_card(rules, frontend);
+        let event = Event::Blackjack {
+            who: Participant::Dealer,
+        };
+        announce(&mut self.players, frontend, &event);
+        let mut round_results: RoundResult = Vec::new();
+        for player in self.players {
+            let results = player
+                .hands()
+                .iter()
+                .map(|hand| {
+                    if hand.surrendered {
+                        PlayerRoundResult::Surrender
+                    } else if hand.took_even_money {
+                        PlayerRoundResult::EvenMoney
+                    } else if hand.is_natural() {
+                        PlayerRoundResult::Standoff
+                    } else {
+                        PlayerRoundResult::Lose
+                    }
+                })
+                .collect();
+            round_results.push((player, results));
+        }
+        IntermediateRoundResult::finished(round_results, self.dealer, self.shoe)
+    }
+
+    fn handle_naturals(
+        mut self,
+        rules: &TableRules,
+        frontend: &mut dyn Frontend,
+    ) -> IntermediateRoundResult<D> {
+        // With early surrender, players can give up their hands before the dealer checks for blackjack.
+        let upcard_value = card_value(self.dealer.upcard());
+        if rules.surrender_rule == SurrenderRule::Early && (upcard_value == 1 || upcard_value == 10)
+        {
+            self.offer_early_surrender(frontend);
+        }
+
+        // Players get a chance to insure against a dealer blackjack before the dealer checks for it.
+        if rules.offer_insurance && self.dealer.upcard().rank == cards::Rank::Ace {
+            self.offer_insurance(frontend);
+        }
+
+        // Without a hole card, there's nothing to check until the players are done.
+        if rules.hole_card_policy == HoleCardPolicy::NoHoleCard {
+            return IntermediateRoundResult::Unfinished(self);
+        }
+
+        match self.check_dealer_natural(rules, frontend) {
+            IntermediateRoundResult::Unfinished(game) => self = game,
+            finished => return finished,
+        }
+
+        let all_players_have_blackjack = self
+            .players
+            .iter()
+            .all(|player| player.hands()[0].is_natural());
+        if all_players_have_blackjack {
+            self.reveal_hole_card(rules, frontend);
+            for index in 0..self.players.len() {
+                let event = Event::Blackjack {
+                    who: Participant::Player(self.players[index].name().to_string()),
+                };
+                announce(&mut self.players, frontend, &event);
+            }
+            let round_results = self
+                .players
+                .into_iter()
+                .map(|player| {
+                    let result = decided_result(&player.hands()[0]).unwrap();
+                    (player, vec![result])
+                })
+                .collect();
+            return IntermediateRoundResult::finished(round_results, self.dealer, self.shoe);
+        }
         IntermediateRoundResult::Unfinished(self)
     }
 
-    fn player_turns(&mut self) {
-        for player in &mut self.players {
-            println!("---{}'s turn!---", player.name());
+    fn player_turns(&mut self, rules: &TableRules, frontend: &mut dyn Frontend) {
+        for index in 0..self.players.len() {
+            let name = self.players[index].name().to_string();
+            let event = Event::TurnStarted {
+                player: name.clone(),
+            };
+            announce(&mut self.players, frontend, &event);
+
             // If they had blackjack, they do not take a turn.
-            if hand_is_natural(player.hand()) {
-                self.dealer.show_hand();
-                player.show_hand();
-                println!("Blackjack!");
+            if self.players[index].hands()[0].is_natural() {
+                let event = Event::Blackjack {
+                    who: Participant::Player(name),
+                };
+                announce(&mut self.players, frontend, &event);
+                self.players[index].finish_hand();
                 continue;
             }
 
-            loop {
-                self.dealer.show_hand();
-                player.show_hand(); //# compared to show hands
-                if hand_is_bust(player.hand()) {
-                    println!("Bust!");
-                    break;
+            // Neither do players who already surrendered.
+            if self.players[index].hands()[0].surrendered {
+                continue;
+            }
+
+            // Splitting can give the player more hands, so keep going until they've played them all.
+            let mut rejected_actions = 0;
+            while !self.players[index].finished_all_hands() {
+                let player = &self.players[index];
+                let hand_index = player.active_hand_index();
+                let busted = hand_is_bust(player.hand());
+                let event = Event::HandInPlay {
+                    player: name.clone(),
+                    hand_number: hand_number(player.as_ref(), hand_index),
+                    cards: player.hand().to_vec(),
+                };
+                announce(&mut self.players, frontend, &event);
+
+                if busted {
+                    let event = Event::HandBusted {
+                        who: Participant::Player(name.clone()),
+                    };
+                    announce(&mut self.players, frontend, &event);
+                    self.players[index].finish_hand();
+                    continue;
                 }
-                let turn_over = player.take_turn(&mut self.deck, &self.dealer.hand()[1]);
-                if turn_over {
-                    break;
+
+                let player = &mut self.players[index];
+                let mut allowed_actions = player.allowed_actions(rules);
+                allowed_actions.retain(|&action| self.shoe.can_draw(cards_needed_for(action)));
+                let mut action = player.decide_action(self.dealer.upcard(), &allowed_actions);
+                // Once every card is out on the table, a hand that needs one has to stand as it is.
+                if !self.shoe.can_draw(cards_needed_for(action)) {
+                    action = Action::Stand;
+                }
+                // So does a hand whose player keeps asking for moves they can't make.
+                if rejected_actions >= MAX_REJECTED_ACTIONS {
+                    action = Action::Stand;
+                }
+                match player.handle_player_action(action, &mut self.shoe, rules) {
+                    Ok(hand_over) => {
+                        rejected_actions = 0;
+                        let dealt = cards_dealt_by(action, player.as_ref(), hand_index);
+                        let event = Event::ActionTaken {
+                            who: Participant::Player(name.clone()),
+                            action,
+                        };
+                        announce(&mut self.players, frontend, &event);
+                        for card in dealt {
+                            let event = Event::CardDealt {
+                                to: Participant::Player(name.clone()),
+                                card: Some(card),
+                            };
+                            announce(&mut self.players, frontend, &event);
+                        }
+                        if hand_over {
+                            self.players[index].finish_hand();
+                        }
+                    }
+                    Err(reason) => {
+                        rejected_actions += 1;
+                        let event = Event::ActionRejected {
+                            player: name.clone(),
+                            reason,
+                        };
+                        announce(&mut self.players, frontend, &event);
+                    }
                 }
-                println!("")
             }
         }
     }
 
-    fn check_if_all_players_finished(self) -> IntermediateRoundResult<D> {
-        let all_done: bool = self.players[..]
-            .into_iter()
-            .all(|player| hand_is_bust(player.hand()) || hand_is_natural(player.hand()));
+    /// Without a hole card, the dealer gets their second card after the players are done, and
+    /// only then can anyone find out if they have blackjack.
+    fn deal_dealer_second_card(
+        mut self,
+        rules: &TableRules,
+        frontend: &mut dyn Frontend,
+    ) -> IntermediateRoundResult<D> {
+        // If every card is already out on the table, the dealer plays with just the one.
+        if let Ok(deal) = self.shoe.draw() {
+            self.dealer.recieve_card(deal);
+            let event = Event::CardDealt {
+                to: Participant::Dealer,
+                card: Some(deal),
+            };
+            announce(&mut self.players, frontend, &event);
+        }
+        self.check_dealer_natural(rules, frontend)
+    }
+
+    fn check_if_all_players_finished(
+        mut self,
+        rules: &TableRules,
+        frontend: &mut dyn Frontend,
+    ) -> IntermediateRoundResult<D> {
+        let all_done: bool = self.players.iter().all(|player| {
+            player
+                .hands()
+                .iter()
+                .all(|hand| decided_result(hand).is_some())
+        });
 
         if all_done {
+            // The dealer doesn't need to play, but still turns over their hole card at the end of the round.
+            self.reveal_hole_card(rules, frontend);
             let mut round_results: RoundResult = Vec::new();
             for player in self.players {
-                if hand_is_natural(player.hand()) {
-                    round_results.push((player, PlayerRoundResult::Natural))
-                } else {
-                    round_results.push((player, PlayerRoundResult::Lose))
-                }
+                let results = player.hands().iter().filter_map(decided_result).collect();
+                round_results.push((player, results));
             }
-            return IntermediateRoundResult::Finished {
-                results: round_results,
-                leftover_deck: self.deck,
-            };
+            return IntermediateRoundResult::finished(round_results, self.dealer, self.shoe);
         }
         IntermediateRoundResult::Unfinished(self)
     }
 
-    fn dealer_turn(mut self) -> IntermediateRoundResult<D> {
-        println!("---Dealer's turn!---");
+    fn dealer_turn(
+        mut self,
+        rules: &TableRules,
+        frontend: &mut dyn Frontend,
+    ) -> IntermediateRoundResult<D> {
+        announce(&mut self.players, frontend, &Event::DealerTurnStarted);
+        self.reveal_hole_card(rules, frontend);
         loop {
-            self.dealer.show_true_hand();
             if hand_is_bust(self.dealer.hand()) {
-                println!("Dealer goes bust!");
+                let event = Event::HandBusted {
+                    who: Participant::Dealer,
+                };
+                announce(&mut self.players, frontend, &event);
                 let mut round_results: RoundResult = Vec::new();
                 for player in self.players {
-                    if hand_is_bust(player.hand()) {
-                        round_results.push((player, PlayerRoundResult::Lose))
-                    } else {
-                        round_results.push((player, PlayerRoundResult::Win))
-                    }
+                    let results = player
+                        .hands()
+                        .iter()
+                        .map(|hand| decided_result(hand).unwrap_or(PlayerRoundResult::Win))
+                        .collect();
+                    round_results.push((player, results));
                 }
-                return IntermediateRoundResult::Finished {
-                    results: round_results,
-                    leftover_deck: self.deck,
-                };
+                return IntermediateRoundResult::finished(round_results, self.dealer, self.shoe);
             }
-            let turn_over = self.dealer.take_turn(&mut self.deck);
+
+            let action = self.dealer.decide_action(rules);
+            let event = Event::ActionTaken {
+                who: Participant::Dealer,
+                action,
+            };
+            announce(&mut self.players, frontend, &event);
+            let turn_over = self.dealer.handle_dealer_action(action, &mut self.shoe);
             if turn_over {
                 break;
             }
+            let event = Event::CardDealt {
+                to: Participant::Dealer,
+                card: self.dealer.hand().last().copied(),
+            };
+            announce(&mut self.players, frontend, &event);
         }
-        return IntermediateRoundResult::Unfinished(self);
+        IntermediateRoundResult::Unfinished(self)
     }
 
-    fn complete_round(self) -> (RoundResult, cards::Deck) {
+    fn complete_round(mut self) -> (RoundResult, cards::Shoe) {
         let mut round_results: RoundResult = Vec::new();
+        let dealer_value = hand_value(self.dealer.hand());
 
         for player in self.players {
-            // If a player had blackjack, they win even if the dealer got to 21 themselves later.
-            // If dealer had blackjack, then the game would've ended before this call.
-            if hand_is_natural(player.hand()) {
-                round_results.push((player, PlayerRoundResult::Win));
-                continue;
-            }
-
-            // If a player is bust then they lose.
-            if hand_is_bust(player.hand()) {
-                round_results.push((player, PlayerRoundResult::Lose));
-                continue;
-            }
+            let results = player
+                .hands()
+                .iter()
+                .map(|hand| {
+                    // If a player had blackjack, they win even if the dealer got to 21 themselves later.
+                    // If dealer had blackjack, then the game would've ended before this call.
+                    // Busted and surrendered hands have already lost too.
+                    if let Some(result) = decided_result(hand) {
+                        return result;
+                    }
 
-            match hand_value(player.hand()).cmp(&hand_value(self.dealer.hand())) {
-                Ordering::Less => round_results.push((player, PlayerRoundResult::Lose)),
-                Ordering::Greater => round_results.push((player, PlayerRoundResult::Win)),
-                Ordering::Equal => round_results.push((player, PlayerRoundResult::Standoff)),
-            }
+                    match hand_value(&hand.cards).cmp(&dealer_value) {
+                        Ordering::Less => PlayerRoundResult::Lose,
+                        Ordering::Greater => PlayerRoundResult::Win,
+                        Ordering::Equal => PlayerRoundResult::Standoff,
+                    }
+                })
+                .collect();
+            round_results.push((player, results));
         }
-        (round_results, self.deck)
+        self.dealer.discard_hand(&mut self.shoe);
+        (round_results, self.shoe)
     }
 
-    fn play_round(mut self) -> (RoundResult, cards::Deck) {
+    fn play_round(
+        mut self,
+        rules: &TableRules,
+        frontend: &mut dyn Frontend,
+    ) -> (RoundResult, cards::Shoe) {
         // Check if anybody has blackjack, and handle it appropriately.
-        let natural_results = self.handle_naturals();
+        let natural_results = self.handle_naturals(rules, frontend);
         match natural_results {
-            IntermediateRoundResult::Finished {
-                results,
-                leftover_deck,
-            } => return (results, leftover_deck),
+            IntermediateRoundResult::Finished { results, shoe } => return (results, shoe),
             IntermediateRoundResult::Unfinished(game) => self = game,
         }
 
-        // Let the players take their turns, and check if the game is over.
-        self.player_turns();
-        let player_turn_results = self.check_if_all_players_finished();
+        // Let the players take their turns.
+        self.player_turns(rules, frontend);
+
+        // Without a hole card, the dealer's second card decides whether they have blackjack.
+        if rules.hole_card_policy == HoleCardPolicy::NoHoleCard {
+            match self.deal_dealer_second_card(rules, frontend) {
+                IntermediateRoundResult::Finished { results, shoe } => return (results, shoe),
+                IntermediateRoundResult::Unfinished(game) => self = game,
+            }
+        }
+
+        // Check if the game is over.
+        let player_turn_results = self.check_if_all_players_finished(rules, frontend);
         match player_turn_results {
-            IntermediateRoundResult::Finished {
-                results,
-                leftover_deck,
-            } => return (results, leftover_deck),
+            IntermediateRoundResult::Finished { results, shoe } => return (results, shoe),
             IntermediateRoundResult::Unfinished(game) => self = game,
         }
 
         // Let the dealer make their turn. Will end if they go bust.
-        let dealer_turn_results = self.dealer_turn();
+        let dealer_turn_results = self.dealer_turn(rules, frontend);
         match dealer_turn_results {
-            IntermediateRoundResult::Finished {
-                results,
-                leftover_deck,
-            } => return (results, leftover_deck),
+            IntermediateRoundResult::Finished { results, shoe } => return (results, shoe),
             IntermediateRoundResult::Unfinished(game) => self = game,
         }
 
@@ -331,11 +669,62 @@ where
     }
 }
 
+/// Gets the result for a hand that doesn't depend on what the dealer ends up with (as long as the
+/// dealer doesn't have a natural): surrendered hands, naturals, and busted hands.
+/// Returns None if the hand has to be compared against the dealer's.
+fn decided_result(hand: &players::PlayerHand) -> Option<PlayerRoundResult> {
+    if hand.surrendered {
+        Some(PlayerRoundResult::Surrender)
+    } else if hand.took_even_money {
+        Some(PlayerRoundResult::EvenMoney)
+    } else if hand.is_natural() {
+        Some(PlayerRoundResult::Natural)
+    } else if hand_is_bust(&hand.cards) {
+        Some(PlayerRoundResult::Lose)
+    } else {
+        None
+    }
+}
+
+/// Gets the number shown for one of a player's hands (counting from 1), or None if they only have the one hand.
+fn hand_number(player: &dyn Player, hand_index: usize) -> Option<usize> {
+    if player.hands().len() > 1 {
+        Some(hand_index + 1)
+    } else {
+        None
+    }
+}
+
+/// Gets how many cards get dealt when a player takes an action.
+fn cards_needed_for(action: Action) -> usize {
+    match action {
+        Action::Hit | Action::DoubleDown => 1,
+        Action::Split => 2,
+        Action::Stand | Action::Surrender => 0,
+    }
+}
+
+/// Gets the cards a player was just dealt by taking an action on the hand at `hand_index`.
+/// Splitting deals one card to each of the two hands it makes.
+fn cards_dealt_by(action: Action, player: &dyn Player, hand_index: usize) -> Vec<cards::Card> {
+    let hands = player.hands();
+    match action {
+        Action::Hit | Action::DoubleDown => vec![*hands[hand_index].cards.last().unwrap()],
+        Action::Split => vec![hands[hand_index].cards[1], hands[hand_index + 1].cards[1]],
+        Action::Stand | Action::Surrender => Vec::new(),
+    }
+}
+
 /// Given a card, return it's numeric value in Blackjack.
 /// Aces count as 1, and will get the extra 10 if it doesn't make the player go bust
 /// when taking their whole hand value into account.
 pub fn card_value(card: &cards::Card) -> u32 {
-    match card.rank {
+    rank_value(card.rank)
+}
+
+/// The numeric value in Blackjack of a card with the given rank, counting aces as 1.
+pub const fn rank_value(rank: cards::Rank) -> u32 {
+    match rank {
         cards::Rank::Ace => 1,
         cards::Rank::Two => 2,
         cards::Rank::Three => 3,
@@ -354,8 +743,7 @@ pub fn card_value(card: &cards::Card) -> u32 {
 
 /// For a slice of cards, get the raw value of the hand (not counting aces potentially as 11)
 pub fn raw_hand_value(hand: &[cards::Card]) -> u32 {
-    let values: Vec<u32> = hand.iter().map(|card| card_value(card)).collect();
-    values.iter().sum()
+    hand.iter().map(card_value).sum()
 }
 
 /// Return true if the hand has an ace that can be counted as 11.
@@ -373,307 +761,499 @@ pub fn hand_value(hand: &[cards::Card]) -> u32 {
     }
 }
 
+/// For a hand kept as counts of each rank, get the raw value of the hand (not counting aces
+/// potentially as 11). Like the rest of the composition functions, this never allocates.
+pub fn raw_composition_value(hand: &cards::compact::Composition) -> u32 {
+    hand.iter()
+        .map(|(rank, count)| rank_value(rank) * u32::from(count))
+        .sum()
+}
+
+/// Return true if the hand, kept as counts of each rank, has an ace that can be counted as 11.
+pub fn is_soft_composition(raw_value: u32, hand: &cards::compact::Composition) -> bool {
+    raw_value <= 11 && hand.count(cards::Rank::Ace) > 0
+}
+
+/// For a hand kept as counts of each rank, return the value of the hand (properly handling Aces)
+pub fn composition_value(hand: &cards::compact::Composition) -> u32 {
+    let raw_value = raw_composition_value(hand);
+    if is_soft_composition(raw_value, hand) {
+        raw_value + 10
+    } else {
+        raw_value
+    }
+}
+
 /// For a slice of cards, return true if the value of the hand is exactly 21 and there are only 2 cards in the hand.
 pub fn hand_is_natural(hand: &[cards::Card]) -> bool {
-    hand_value(&hand) == 21 && hand.len() == 2
+    hand_value(hand) == 21 && hand.len() == 2
 }
 
 /// For a slice of cards, return true if the value of the hand is over 21.
 pub fn hand_is_bust(hand: &[cards::Card]) -> bool {
-    hand_value(&hand) > 21
+    hand_value(hand) > 21
 }
 
-/// Settles the round--goes over the results (and bets once those are added)
-fn settle_round(round_results: RoundResult, &payout_ratio: &f64) -> Vec<Box<dyn Player>> {
-    println!("");
-    let mut new_players: Vec<Box<dyn Player>> = Vec::new();
-    for (mut player, result) in round_results {
-        //player.show_hand();
-        player.discard_hand();
-        player.handle_round_result(result, payout_ratio);
-        new_players.push(player);
-    }
-    new_players
+/// For a slice of cards, return true if it is exactly two cards of the same value (and so could be split).
+/// Tens and face cards all count as the same value, so a Jack and a King are a pair.
+pub fn hand_is_pair(hand: &[cards::Card]) -> bool {
+    hand.len() == 2 && card_value(&hand[0]) == card_value(&hand[1])
 }
 
-/// Gets the point at which the deck needs to be reshuffled. Basically acts like the
-/// plastic card in a deck in a casino--if the deck length is below this number
-/// then we need to get a new full reshuffled deck for the next game.__rust_force_expr!
-///
-/// # Arguments
-///
-/// * `num_decks` - number of decks used to create the deck for the game. Should be same
-/// value that's passed into `cards::create_multideck(num_decks)`
-fn reshuffle_number(num_decks: u32) -> u32 {
-    let deck_card_count = u32::try_from(cards::STANDARD_DECK_COUNT).unwrap();
-    cmp::max(40, num_decks * deck_card_count / 5)
-}
+/// Settles the round--pays out (or takes) the bets on every hand based on its result--and
+/// clears the players' cards off the table.
+fn settle_round(
+    round_results: RoundResult,
+    payout_ratio: f64,
+    shoe: &mut cards::Shoe,
+    frontend: &mut dyn Frontend,
+) -> Vec<Box<dyn Player>> {
+    let (mut players, all_results): (Vec<Box<dyn Player>>, Vec<Vec<PlayerRoundResult>>) =
+        round_results.into_iter().unzip();
+    let mut settled = Vec::new();
+    for (index, results) in all_results.into_iter().enumerate() {
+        let player = &mut players[index];
+        let bets: Vec<Option<u32>> = player.hands().iter().map(|hand| hand.bet).collect();
+        let winnings = player.handle_round_result(&results, payout_ratio);
 
-fn should_play_another_round() -> bool {
-    println!("\nPlay another round? [Y/n]");
+        let events: Vec<Event> = results
+            .iter()
+            .enumerate()
+            .map(|(hand_index, &result)| Event::HandSettled {
+                player: player.name().to_string(),
+                hand_number: hand_number(player.as_ref(), hand_index),
+                result,
+                bet: bets[hand_index],
+                winnings: winnings[hand_index],
+                money: player.money(),
+            })
+            .collect();
+        settled.push((player.name().to_string(), results));
+        for event in &events {
+            announce(&mut players, frontend, event);
+        }
+        players[index].discard_hands(shoe);
+    }
+    announce(
+        &mut players,
+        frontend,
+        &Event::RoundSettled { results: settled },
+    );
+    players
+}
 
-    loop {
-        let mut input = String::new();
+/// Tells the front end about something that happened, and shows it to every player at the
+/// table too, so players can follow along with the cards dealt to everyone.
+fn announce(players: &mut [Box<dyn Player>], frontend: &mut dyn Frontend, event: &Event) {
+    frontend.handle_event(event);
+    for player in players {
+        player.observe(event);
+    }
+}
 
-        io::stdin()
-            .read_line(&mut input)
-            .expect("Failed to read line");
+/// Plays a single round of blackjack dealt from the given shoe, without shuffling it first.
+/// Dealing from a [`cards::Shoe::stacked`] shoe makes it possible to play out exact
+/// situations, and [`Scenario`] is an easier way to build one.
+///
+/// The round panics if the shoe runs out of cards before everyone has their first two.
+/// After that, a hand that needs a card once every card is out on the table stands as it is.
+pub fn play_round_with_shoe<D>(
+    players: Vec<Box<dyn Player>>,
+    rules: &TableRules,
+    frontend: &mut dyn Frontend,
+    shoe: cards::Shoe,
+) -> RoundOutcome
+where
+    D: Dealer,
+{
+    let game: ReadyGame<D> = ReadyGame {
+        players,
+        dealer: D::new(),
+        shoe,
+    };
 
-        let input = input.trim();
+    let round = game.deal_hands(rules, frontend);
+    let (round_results, mut shoe) = round.play_round(rules, frontend);
+    let results = round_results
+        .iter()
+        .map(|(_, results)| results.clone())
+        .collect();
+    let players = settle_round(round_results, rules.payout_ratio, &mut shoe, frontend);
 
-        match &input.to_lowercase()[..] {
-            "" | "yes" | "y" => return true,
-            "n" | "No" | "q" | "quit" | "e" | "exit" => return false,
-            _ => println!(
-                "Sorry, what was that? (try yes, no, exit, or the first letters of any of those."
-            ),
-        }
+    RoundOutcome {
+        players,
+        results,
+        shoe,
     }
 }
 
-/// From a GameOptions describing the settings of the game, play a full game of blackjack.
-/// Takes a dealer type, which is the dealer that the game will use.
+/// Plays a game of blackjack with the given players, using a GameOptions describing the settings
+/// of the game. Takes a dealer type, which is the dealer that the game will use, and a front end
+/// that is told everything that happens and decides when to stop playing. Returns the players
+/// once the game is over. To let other observers follow along, subscribe them to an
+/// [`EventStream`] wrapping the front end and play with that instead.
 ///
 /// # Example
 ///
 /// ```
 /// use praeses_blackjack::blackjack;
+/// use praeses_blackjack::blackjack::actors::dealers::StandardDealer;
+/// use praeses_blackjack::blackjack::actors::players::AutoPlayer;
+/// use praeses_blackjack::Player;
+///
+/// /// Plays a single round without showing anything.
+/// struct OneRound;
+///
+/// impl blackjack::Frontend for OneRound {
+///     fn handle_event(&mut self, _event: &blackjack::Event) {}
+///
+///     fn play_another_round(&mut self) -> bool {
+///         false
+///     }
+/// }
 ///
 /// let options = blackjack::GameOptions {
-/// num_players: 1,
-/// bot_player: false,
-/// num_decks: 6,
-/// betting_buy_in: 500,
-/// payout_ratio: 1.5,
+///     betting_buy_in: 500,
+///     rules: blackjack::TableRules::vegas_strip(),
+///     ..Default::default()
 /// };
+/// let players: Vec<Box<dyn Player>> = vec![Box::new(AutoPlayer::new(options.betting_buy_in))];
 ///
-/// // blackjack::play_blackjack::<blackjack::player::Dealer>(options);
+/// let players = blackjack::play_blackjack::<StandardDealer>(players, &options, &mut OneRound);
+/// assert_eq!(1, players.len());
 /// ```
-pub fn play_blackjack<D>(options: GameOptions)
+pub fn play_blackjack<D>(
+    players: Vec<Box<dyn Player>>,
+    options: &GameOptions,
+    frontend: &mut dyn Frontend,
+) -> Vec<Box<dyn Player>>
 where
     D: Dealer,
 {
-    let mut game: ReadyGame<D> = ReadyGame::new(&options);
+    play_blackjack_with_rng::<D, _>(players, options, frontend, &mut rand::thread_rng())
+}
+
+/// Plays a game of blackjack just like [`play_blackjack`], but every shuffle comes from the
+/// given random number generator. With a seeded generator (like [`cards::seeded_rng`]), the
+/// same seed and the same decisions always play out exactly the same game.
+///
+/// # Example
+///
+/// ```
+/// use praeses_blackjack::blackjack::{self, actors::dealers::StandardDealer};
+/// use praeses_blackjack::cards;
+///
+/// struct OneRound;
+///
+/// impl blackjack::Frontend for OneRound {
+///     fn handle_event(&mut self, _event: &blackjack::Event) {}
+///
+///     fn play_another_round(&mut self) -> bool {
+///         false
+///     }
+/// }
+///
+/// let options = blackjack::GameOptions::default();
+/// let mut rng = cards::seeded_rng(2022);
+/// blackjack::play_blackjack_with_rng::<StandardDealer, _>(Vec::new(), &options, &mut OneRound, &mut rng);
+/// ```
+pub fn play_blackjack_with_rng<D, R>(
+    players: Vec<Box<dyn Player>>,
+    options: &GameOptions,
+    frontend: &mut dyn Frontend,
+    rng: &mut R,
+) -> Vec<Box<dyn Player>>
+where
+    D: Dealer,
+    R: Rng + ?Sized,
+{
+    let mut game: ReadyGame<D> = ReadyGame::new(players, options, rng);
 
     loop {
-        let round = game.deal_hands();
+        let round = game.deal_hands(&options.rules, frontend);
 
-        let finished_round = round.play_round();
+        let finished_round = round.play_round(&options.rules, frontend);
 
-        let (round_results, leftover_deck) = finished_round;
+        let (round_results, mut shoe) = finished_round;
 
-        let next_players = settle_round(round_results, &options.payout_ratio);
+        let next_players = settle_round(
+            round_results,
+            options.rules.payout_ratio,
+            &mut shoe,
+            frontend,
+        );
 
         // Check if they want to play another round.
-        // Optionally continue playing rounds (and add/drop players?)
-        if should_play_another_round() {
-            println!("");
-            game = ReadyGame::from_previous_round(next_players, leftover_deck, &options);
-        } else {
-            break;
+        if !frontend.play_another_round() {
+            return next_players;
         }
-    }
 
-    println!("Thanks for playing!")
+        game = ReadyGame::from_previous_round(next_players, shoe, options, frontend);
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::blackjack::actors::dealers::StandardDealer;
+    use crate::blackjack::actors::players::AutoPlayer;
+
+    /// A front end that keeps every event it sees and stops after a set number of rounds.
+    struct RecordingFrontend {
+        events: Vec<Event>,
+        rounds_left: u32,
+    }
+
+    impl Frontend for RecordingFrontend {
+        fn handle_event(&mut self, event: &Event) {
+            self.events.push(event.clone());
+        }
+
+        fn play_another_round(&mut self) -> bool {
+            self.rounds_left -= 1;
+            self.rounds_left > 0
+        }
+    }
+
+    /// Plays some rounds between two bots with shuffles from the given seed, returning every event from the game.
+    fn record_game(rules: TableRules, rounds: u32, seed: u64) -> Vec<Event> {
+        record_game_with(
+            GameOptions {
+                betting_buy_in: 0,
+                rules,
+                ..GameOptions::default()
+            },
+            rounds,
+            seed,
+        )
+    }
+
+    /// Plays some rounds between two bots like [`record_game`], with any game options.
+    fn record_game_with(options: GameOptions, rounds: u32, seed: u64) -> Vec<Event> {
+        let players: Vec<Box<dyn Player>> = vec![
+            Box::new(AutoPlayer::new(options.betting_buy_in)),
+            Box::new(AutoPlayer::new(options.betting_buy_in)),
+        ];
+        let mut frontend = RecordingFrontend {
+            events: Vec::new(),
+            rounds_left: rounds,
+        };
+
+        let mut rng = cards::seeded_rng(seed);
+        let players = play_blackjack_with_rng::<StandardDealer, _>(
+            players,
+            &options,
+            &mut frontend,
+            &mut rng,
+        );
+        assert_eq!(2, players.len());
+        frontend.events
+    }
+
+    fn count_events(events: &[Event], matches: impl Fn(&Event) -> bool) -> usize {
+        events.iter().filter(|&event| matches(event)).count()
+    }
 
     #[test]
-    fn hand_value_correct() {
+    fn plays_rounds_without_a_terminal() {
+        let rounds = 50;
+        let events = record_game(TableRules::default(), rounds, 1);
+
         assert_eq!(
-            21,
-            hand_value(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Spade
-                }
-            ])
+            Some(&Event::RoundStarted {
+                cards_seen: 0,
+                cards_remaining: 6 * cards::STANDARD_DECK_COUNT - 1
+            }),
+            events.first()
         );
         assert_eq!(
-            18,
-            hand_value(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::Seven,
-                    suit: cards::Suit::Diamond
-                },
-                cards::Card {
-                    rank: cards::Rank::Jack,
-                    suit: cards::Suit::Heart
-                }
-            ])
+            rounds as usize,
+            count_events(&events, |event| matches!(event, Event::RoundStarted { .. }))
         );
-        assert_eq!(
-            20,
-            hand_value(&[
-                cards::Card {
-                    rank: cards::Rank::Queen,
-                    suit: cards::Suit::Heart
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Diamond
+        assert!(matches!(events.last(), Some(Event::RoundSettled { .. })));
+
+        // Every card from every round goes into the discard tray, so none go missing from the shoe.
+        for event in &events {
+            if let Event::RoundStarted {
+                cards_seen,
+                cards_remaining,
+            } = event
+            {
+                assert_eq!(
+                    6 * cards::STANDARD_DECK_COUNT,
+                    cards_seen + cards_remaining + 1
+                );
+            }
+        }
+
+        // The hole card is dealt face down every round, and always turned over by the end of it.
+        let hole_cards = count_events(&events, |event| {
+            matches!(
+                event,
+                Event::CardDealt {
+                    to: Participant::Dealer,
+                    card: None
                 }
-            ])
+            )
+        });
+        let reveals = count_events(&events, |event| {
+            matches!(event, Event::HoleCardRevealed { .. })
+        });
+        assert_eq!(rounds as usize, hole_cards);
+        assert_eq!(rounds as usize, reveals);
+
+        // Every hand gets settled, and bots without money never bet.
+        let settled = count_events(&events, |event| {
+            matches!(event, Event::HandSettled { bet: None, .. })
+        });
+        assert!(settled >= 2 * rounds as usize);
+        assert_eq!(
+            0,
+            count_events(&events, |event| matches!(event, Event::BetPlaced { .. }))
         );
     }
 
+    #[test]
+    fn same_seed_replays_the_same_game() {
+        // Enough rounds to go through a reshuffle.
+        let rules = TableRules {
+            num_decks: 1,
+            ..TableRules::default()
+        };
+        let events = record_game(rules.clone(), 30, 2022);
+        assert!(events.contains(&Event::ShoeReshuffled));
+
+        assert_eq!(events, record_game(rules.clone(), 30, 2022));
+        assert_ne!(events, record_game(rules, 30, 2023));
+    }
+
+    #[test]
+    fn reshuffles_before_the_shoe_runs_too_low() {
+        // Without reshuffling early, the cut card wouldn't come out until the shoe was empty.
+        let rules = TableRules {
+            num_decks: 1,
+            penetration: cards::Penetration::Percent(100.0),
+            ..TableRules::default()
+        };
+        let events = record_game(rules, 30, 2022);
+
+        assert!(events.contains(&Event::ShoeReshuffled));
+        assert!(events.iter().all(|event| match event {
+            Event::RoundStarted {
+                cards_remaining, ..
+            } => *cards_remaining >= CARDS_PER_HAND * 3,
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn continuous_shuffler_never_runs_the_shoe_down() {
+        let options = GameOptions {
+            betting_buy_in: 0,
+            rules: TableRules {
+                num_decks: 1,
+                ..TableRules::default()
+            },
+            shuffle_mode: cards::ShuffleMode::Continuous,
+            ..GameOptions::default()
+        };
+        let events = record_game_with(options, 30, 2022);
+
+        assert!(!events.contains(&Event::ShoeReshuffled));
+        assert!(events.iter().all(|event| match event {
+            Event::RoundStarted {
+                cards_seen,
+                cards_remaining,
+            } => *cards_seen == 0 && *cards_remaining == cards::STANDARD_DECK_COUNT - 1,
+            _ => true,
+        }));
+    }
+
+    #[test]
+    fn games_deal_from_custom_decks() {
+        let options = GameOptions {
+            betting_buy_in: 0,
+            deck: cards::DeckBuilder::spanish(),
+            ..GameOptions::default()
+        };
+        let events = record_game_with(options, 100, 48);
+
+        assert!(events.iter().all(|event| match event {
+            Event::CardDealt {
+                card: Some(card), ..
+            }
+            | Event::HoleCardRevealed { card } => card.rank != cards::Rank::Ten,
+            Event::RoundStarted {
+                cards_seen,
+                cards_remaining,
+            } => cards_seen + cards_remaining <= 6 * 48,
+            _ => true,
+        }));
+        assert!(events.contains(&Event::ShoeReshuffled));
+    }
+
+    #[test]
+    fn no_hole_card_games_deal_every_card_face_up() {
+        let events = record_game(TableRules::european(), 50, 2);
+
+        assert!(events.iter().all(|event| !matches!(
+            event,
+            Event::CardDealt { card: None, .. } | Event::HoleCardRevealed { .. }
+        )));
+    }
+
+    /// Reads a hand written out like "Ac Ks".
+    fn hand(text: &str) -> cards::Hand {
+        text.parse::<cards::notation::ParsedHand>().unwrap().into()
+    }
+
+    #[test]
+    fn hand_value_correct() {
+        assert_eq!(21, hand_value(&hand("Ac Ks")));
+        assert_eq!(18, hand_value(&hand("Ac 7d Jh")));
+        assert_eq!(20, hand_value(&hand("Qh Kd")));
+    }
+
     #[test]
     fn detects_naturals() {
-        assert_eq!(
-            true,
-            hand_is_natural(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Spade
-                }
-            ])
-        );
-        assert_eq!(
-            false,
-            hand_is_natural(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::Seven,
-                    suit: cards::Suit::Diamond
-                },
-                cards::Card {
-                    rank: cards::Rank::Three,
-                    suit: cards::Suit::Heart
-                }
-            ])
-        );
-        assert_eq!(
-            false,
-            hand_is_natural(&[
-                cards::Card {
-                    rank: cards::Rank::Queen,
-                    suit: cards::Suit::Heart
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Diamond
-                }
-            ])
-        );
+        assert!(hand_is_natural(&hand("Ac Ks")));
+        assert!(!hand_is_natural(&hand("Ac 7d 3h")));
+        assert!(!hand_is_natural(&hand("Qh Kd")));
     }
 
     #[test]
     fn detects_busts() {
-        assert_eq!(
-            false,
-            hand_is_bust(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Spade
-                }
-            ])
-        );
-        assert_eq!(
-            false,
-            hand_is_bust(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::Seven,
-                    suit: cards::Suit::Diamond
-                },
-                cards::Card {
-                    rank: cards::Rank::Four,
-                    suit: cards::Suit::Heart
-                }
-            ])
-        );
-        assert_eq!(
-            true,
-            hand_is_bust(&[
-                cards::Card {
-                    rank: cards::Rank::Ace,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Diamond
-                },
-                cards::Card {
-                    rank: cards::Rank::Nine,
-                    suit: cards::Suit::Heart
-                },
-                cards::Card {
-                    rank: cards::Rank::Seven,
-                    suit: cards::Suit::Heart
-                }
-            ])
-        );
-        assert_eq!(
-            false,
-            hand_is_bust(&[
-                cards::Card {
-                    rank: cards::Rank::Queen,
-                    suit: cards::Suit::Heart
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Diamond
-                }
-            ])
-        );
-        assert_eq!(
-            false,
-            hand_is_bust(&[
-                cards::Card {
-                    rank: cards::Rank::Two,
-                    suit: cards::Suit::Heart
-                },
-                cards::Card {
-                    rank: cards::Rank::Two,
-                    suit: cards::Suit::Diamond
-                }
-            ])
-        );
-        assert_eq!(
-            true,
-            hand_is_bust(&[
-                cards::Card {
-                    rank: cards::Rank::Queen,
-                    suit: cards::Suit::Heart
-                },
-                cards::Card {
-                    rank: cards::Rank::King,
-                    suit: cards::Suit::Diamond
-                },
-                cards::Card {
-                    rank: cards::Rank::Nine,
-                    suit: cards::Suit::Club
-                },
-                cards::Card {
-                    rank: cards::Rank::Ten,
-                    suit: cards::Suit::Diamond
-                }
-            ])
-        );
+        assert!(!hand_is_bust(&hand("Ac Ks")));
+        assert!(!hand_is_bust(&hand("Ac 7d 4h")));
+        assert!(hand_is_bust(&hand("Ac Kd 9h 7h")));
+        assert!(!hand_is_bust(&hand("Qh Kd")));
+        assert!(!hand_is_bust(&hand("2h 2d")));
+        assert!(hand_is_bust(&hand("Qh Kd 9c Td")));
+    }
+
+    #[test]
+    fn composition_values_match_hand_values() {
+        for text in [
+            "Ac Ks",
+            "Ac 7d Jh",
+            "Qh Kd",
+            "A A 9",
+            "A A A A 7",
+            "5 6",
+            "Ac Kd 9h 7h",
+        ] {
+            let hand = hand(text);
+            let composition = cards::compact::Composition::from(&hand[..]);
+            assert_eq!(
+                hand_value(&hand),
+                composition_value(&composition),
+                "{}",
+                text
+            );
+            assert_eq!(
+                is_soft_hand(raw_hand_value(&hand), &hand),
+                is_soft_composition(raw_composition_value(&composition), &composition)
+            );
+        }
     }
 }
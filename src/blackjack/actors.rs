@@ -5,33 +5,91 @@
 pub mod dealers;
 pub mod players;
 
+use std::fmt;
+
 use crate::cards;
 
 /// Supported player actions.
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub enum Action {
     /// Adds a card from the deck to hand.
     Hit,
     /// Keep the cards in hand and pass to the next player.
     Stand,
+    /// Double the bet on the hand, take exactly one more card, and end the turn.
+    DoubleDown,
+    /// Split a pair into two hands, each with its own bet, and play them one after the other.
+    Split,
+    /// Give up the hand and get back half of the bet.
+    Surrender,
 }
 
 impl Action {
-    /// Provides a default prompt for actions in the commandline.
-    pub const ACTION_PROMPT: &'static str = "Hit (h) or Stand (s)?";
+    /// Given an action, return the name shown for it in the commandline.
+    const fn name(&self) -> &str {
+        match self {
+            Self::Hit => "Hit",
+            Self::Stand => "Stand",
+            Self::DoubleDown => "Double down",
+            Self::Split => "Split",
+            Self::Surrender => "Surrender",
+        }
+    }
+
+    /// Given an action, return the shortcut players can type to take it in the commandline.
+    const fn abbreviation(&self) -> &str {
+        match self {
+            Self::Hit => "h",
+            Self::Stand => "s",
+            Self::DoubleDown => "d",
+            Self::Split => "p",
+            Self::Surrender => "r",
+        }
+    }
+
+    /// Provides a prompt for actions in the commandline, only offering the actions that are allowed.
+    pub fn action_prompt(allowed_actions: &[Self]) -> String {
+        let choices: Vec<String> = allowed_actions
+            .iter()
+            .map(|action| format!("{} ({})", action.name(), action.abbreviation()))
+            .collect();
 
-    /// From an input string, return an action if there is an appropriate match found.
-    /// If not, return an error.
-    pub fn parse_from_string(input: &str) -> Result<Self, &'static str> {
+        match choices.split_last() {
+            None => String::new(),
+            Some((last, [])) => format!("{}?", last),
+            Some((last, [first])) => format!("{} or {}?", first, last),
+            Some((last, rest)) => format!("{}, or {}?", rest.join(", "), last),
+        }
+    }
+
+    /// From an input string, return an action if there is an appropriate match found
+    /// and it is one of the allowed actions. If not, return an error.
+    pub fn parse_from_string(input: &str, allowed_actions: &[Self]) -> Result<Self, &'static str> {
         let input = &input.trim().to_lowercase()[..];
-        match input {
-            "hit" | "h" => Ok(Self::Hit),
-            "stand" | "s" => Ok(Self::Stand),
-            _ => Err("Invalid action input"),
+        let action = match input {
+            "hit" | "h" => Self::Hit,
+            "stand" | "s" => Self::Stand,
+            "double" | "double down" | "d" => Self::DoubleDown,
+            "split" | "p" => Self::Split,
+            "surrender" | "r" => Self::Surrender,
+            _ => return Err("Invalid action input"),
+        };
+
+        if allowed_actions.contains(&action) {
+            Ok(action)
+        } else {
+            Err("That action isn't allowed right now")
         }
     }
 }
 
+impl fmt::Display for Action {
+    /// Shows the action the way it's named in the commandline, like "Double down".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
 /// General trait for behavior that both players and dealers should implement.
 pub trait Actor {
     /// Get a mutable reference to the actor's hand, all the cards they have.
@@ -40,22 +98,19 @@ pub trait Actor {
     /// Get a slice of all cards from an actor's hand. Read-only (as slices are)
     fn hand(&self) -> &[cards::Card];
 
-    /// Display the actor's current hand in a natural way.
-    fn show_hand(&self);
-
     /// Add a card given in the argument to a actor's hand.
     fn recieve_card(&mut self, card: cards::Card) {
         self.hand_mut().push(card);
     }
 
-    /// Discards all cards in an actor's hand.
-    fn discard_hand(&mut self) {
-        self.hand_mut().clear();
+    /// Discards all cards in an actor's hand into the shoe's discard tray.
+    fn discard_hand(&mut self, shoe: &mut cards::Shoe) {
+        shoe.discard(self.hand_mut().drain(..));
     }
 }
 
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use super::*;
 
     /// Function that tests for any actor whether they properly add a card to their hand.
@@ -119,25 +174,105 @@ mod tests {
     /// Checks that given input is parsed properly
     #[test]
     fn parses_action_from_string() {
-        assert_eq!(Action::parse_from_string("hit").unwrap(), Action::Hit);
-        assert_eq!(Action::parse_from_string("h").unwrap(), Action::Hit);
-        assert_eq!(Action::parse_from_string(" hit").unwrap(), Action::Hit);
-        assert_eq!(Action::parse_from_string("hit ").unwrap(), Action::Hit);
-        assert_eq!(Action::parse_from_string("Hit").unwrap(), Action::Hit);
-        assert_eq!(Action::parse_from_string("HIT").unwrap(), Action::Hit);
-
-        assert_eq!(Action::parse_from_string("stand").unwrap(), Action::Stand);
-        assert_eq!(Action::parse_from_string("s").unwrap(), Action::Stand);
-        assert_eq!(Action::parse_from_string(" stand").unwrap(), Action::Stand);
-        assert_eq!(Action::parse_from_string("stand ").unwrap(), Action::Stand);
-        assert_eq!(Action::parse_from_string("Stand").unwrap(), Action::Stand);
-        assert_eq!(Action::parse_from_string("STAND").unwrap(), Action::Stand);
-
-        assert!(Action::parse_from_string("shmit").is_err());
-        assert!(Action::parse_from_string("stund").is_err());
-        assert!(Action::parse_from_string("hoot").is_err());
-        assert!(Action::parse_from_string("ham").is_err());
-        assert!(Action::parse_from_string("praeses").is_err());
-        assert!(Action::parse_from_string("blake").is_err());
+        let all = [
+            Action::Hit,
+            Action::Stand,
+            Action::DoubleDown,
+            Action::Split,
+            Action::Surrender,
+        ];
+
+        assert_eq!(Action::parse_from_string("hit", &all).unwrap(), Action::Hit);
+        assert_eq!(Action::parse_from_string("h", &all).unwrap(), Action::Hit);
+        assert_eq!(
+            Action::parse_from_string(" hit", &all).unwrap(),
+            Action::Hit
+        );
+        assert_eq!(
+            Action::parse_from_string("hit ", &all).unwrap(),
+            Action::Hit
+        );
+        assert_eq!(Action::parse_from_string("Hit", &all).unwrap(), Action::Hit);
+        assert_eq!(Action::parse_from_string("HIT", &all).unwrap(), Action::Hit);
+
+        assert_eq!(
+            Action::parse_from_string("stand", &all).unwrap(),
+            Action::Stand
+        );
+        assert_eq!(Action::parse_from_string("s", &all).unwrap(), Action::Stand);
+        assert_eq!(
+            Action::parse_from_string(" stand", &all).unwrap(),
+            Action::Stand
+        );
+        assert_eq!(
+            Action::parse_from_string("stand ", &all).unwrap(),
+            Action::Stand
+        );
+        assert_eq!(
+            Action::parse_from_string("Stand", &all).unwrap(),
+            Action::Stand
+        );
+        assert_eq!(
+            Action::parse_from_string("STAND", &all).unwrap(),
+            Action::Stand
+        );
+
+        assert_eq!(
+            Action::parse_from_string("double", &all).unwrap(),
+            Action::DoubleDown
+        );
+        assert_eq!(
+            Action::parse_from_string("d", &all).unwrap(),
+            Action::DoubleDown
+        );
+        assert_eq!(
+            Action::parse_from_string("Double Down", &all).unwrap(),
+            Action::DoubleDown
+        );
+
+        assert_eq!(
+            Action::parse_from_string("split", &all).unwrap(),
+            Action::Split
+        );
+        assert_eq!(Action::parse_from_string("p", &all).unwrap(), Action::Split);
+        assert_eq!(
+            Action::parse_from_string(" Split ", &all).unwrap(),
+            Action::Split
+        );
+
+        assert_eq!(
+            Action::parse_from_string("surrender", &all).unwrap(),
+            Action::Surrender
+        );
+        assert_eq!(
+            Action::parse_from_string("R", &all).unwrap(),
+            Action::Surrender
+        );
+
+        assert!(Action::parse_from_string("shmit", &all).is_err());
+        assert!(Action::parse_from_string("stund", &all).is_err());
+        assert!(Action::parse_from_string("hoot", &all).is_err());
+        assert!(Action::parse_from_string("ham", &all).is_err());
+        assert!(Action::parse_from_string("praeses", &all).is_err());
+        assert!(Action::parse_from_string("blake", &all).is_err());
+    }
+
+    /// Checks that actions that aren't allowed are neither offered nor accepted.
+    #[test]
+    fn only_allows_legal_actions() {
+        let basic = [Action::Hit, Action::Stand];
+        assert_eq!(Action::action_prompt(&basic), "Hit (h) or Stand (s)?");
+        assert!(Action::parse_from_string("d", &basic).is_err());
+        assert!(Action::parse_from_string("p", &basic).is_err());
+
+        let with_double = [Action::Hit, Action::Stand, Action::DoubleDown];
+        assert_eq!(
+            Action::action_prompt(&with_double),
+            "Hit (h), Stand (s), or Double down (d)?"
+        );
+        assert_eq!(
+            Action::parse_from_string("d", &with_double).unwrap(),
+            Action::DoubleDown
+        );
     }
 }
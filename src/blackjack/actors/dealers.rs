@@ -12,13 +12,22 @@ pub trait Dealer: actors::Actor {
     where
         Self: Sized;
 
-    /// Shows the true hand of the dealer (because usually their complete hand will be hidden from players).
-    fn show_true_hand(&self);
+    /// Gets the dealer's face-up card, the one players get to see while they play.
+    /// When the dealer has a hole card it is their first card, so this is the last card
+    /// they were dealt before the players' turns.
+    fn upcard(&self) -> &cards::Card {
+        self.hand().last().unwrap()
+    }
 
     /// Get what action a dealer should take. Should be the same for all dealers
-    /// so a default implementation is provided.
-    fn decide_action(&self) -> actors::Action {
-        if blackjack::hand_value(self.hand()) >= 17 {
+    /// so a default implementation is provided. Dealers stand at 17 or more, except
+    /// for soft 17 at tables where the dealer hits it.
+    fn decide_action(&self, rules: &blackjack::TableRules) -> actors::Action {
+        let hand_value = blackjack::hand_value(self.hand());
+        let soft_17 = hand_value == 17
+            && blackjack::is_soft_hand(blackjack::raw_hand_value(self.hand()), self.hand());
+
+        if hand_value > 17 || (hand_value == 17 && !(soft_17 && rules.dealer_hits_soft_17)) {
             actors::Action::Stand
         } else {
             actors::Action::Hit
@@ -26,61 +35,42 @@ pub trait Dealer: actors::Actor {
     }
 
     /// Carry out a dealer's actions in the game. Dopesn't depend on anything but their cards.
-    /// Returns true or false if they can take another turn or not.
-    fn handle_dealer_action(&mut self, action: actors::Action, deck: &mut cards::Deck) -> bool {
+    /// Returns true if their turn is over, false if they can take another action.
+    /// If every card is already out on the table, they have to stand with what they have.
+    fn handle_dealer_action(&mut self, action: actors::Action, shoe: &mut cards::Shoe) -> bool {
         match action {
-            actors::Action::Hit => {
-                let deal = deck.pop().unwrap();
-                println!("Hit! NEW CARD: {}", deal);
-                self.recieve_card(deal);
-                false
-            }
-            actors::Action::Stand => true,
+            actors::Action::Hit => match shoe.draw() {
+                Ok(deal) => {
+                    self.recieve_card(deal);
+                    false
+                }
+                Err(_) => true,
+            },
+            // Dealers can only hit or stand, anything else ends their turn.
+            _ => true,
         }
     }
-
-    /// Decide what action to take and handle that action. Returns true if they can take another turn.
-    fn take_turn(&mut self, deck: &mut cards::Deck) -> bool {
-        let action = self.decide_action();
-        self.handle_dealer_action(action, deck)
-    }
 }
 
-/// A standard dealer whose output is sent to stdout.
+/// A standard dealer, who plays by the table's rules like every dealer does.
 pub struct StandardDealer {
     hand: cards::Hand,
 }
 
 impl actors::Actor for StandardDealer {
-    fn hand_mut(&mut self) -> &mut Vec<cards::Card> {
+    fn hand_mut(&mut self) -> &mut cards::Hand {
         &mut self.hand
     }
 
     fn hand(&self) -> &[cards::Card] {
         self.hand.as_slice()
     }
-
-    fn show_hand(&self) {
-        print!("Dealer's Cards: **");
-        for card in &self.hand[1..] {
-            print!(", {}", card);
-        }
-        println!("");
-    }
 }
 
 impl Dealer for StandardDealer {
     fn new() -> StandardDealer {
         StandardDealer { hand: Vec::new() }
     }
-
-    fn show_true_hand(&self) {
-        print!("Dealer's Cards: {}", &self.hand[0]);
-        for card in &self.hand[1..] {
-            print!(", {}", card);
-        }
-        println!("     (value: {})", blackjack::hand_value(&self.hand[..]));
-    }
 }
 
 #[cfg(test)]
@@ -88,13 +78,18 @@ mod tests {
     use super::super::tests as actor_tests;
     use super::*;
     use crate::blackjack::actors;
+    use crate::blackjack::actors::Actor;
 
     /// Helper funciton for checking that a dealer's action is proper.
-    fn check_action_from_cards<T: Dealer>(card_values: (u32, u32), action: actors::Action) {
+    fn check_action_from_cards<T: Dealer>(
+        card_values: (u32, u32),
+        rules: &blackjack::TableRules,
+        action: actors::Action,
+    ) {
         let mut dealer = T::new();
         dealer.recieve_card(actor_tests::create_card_from_value(card_values.0));
         dealer.recieve_card(actor_tests::create_card_from_value(card_values.1));
-        assert_eq!(dealer.decide_action(), action);
+        assert_eq!(dealer.decide_action(rules), action);
     }
 
     /// Making sure the dealer can add a card to their hand.
@@ -106,16 +101,49 @@ mod tests {
     /// Check that the dealer's actions follow blackjack rules.
     #[test]
     fn standard_dealer_acts_properly() {
+        let rules = blackjack::TableRules::default();
+
         // Dealer has to stand at 17.
-        check_action_from_cards::<StandardDealer>((7, 10), actors::Action::Stand);
+        check_action_from_cards::<StandardDealer>((7, 10), &rules, actors::Action::Stand);
 
         // Dealer should also stand at a soft 18.
-        check_action_from_cards::<StandardDealer>((7, 1), actors::Action::Stand);
+        check_action_from_cards::<StandardDealer>((7, 1), &rules, actors::Action::Stand);
 
         // Dealer should hit at 12.
-        check_action_from_cards::<StandardDealer>((4, 8), actors::Action::Hit);
+        check_action_from_cards::<StandardDealer>((4, 8), &rules, actors::Action::Hit);
 
         // Dealer should also hit at a soft 13.
-        check_action_from_cards::<StandardDealer>((1, 2), actors::Action::Hit);
+        check_action_from_cards::<StandardDealer>((1, 2), &rules, actors::Action::Hit);
+    }
+
+    /// Check that the dealer stands on soft 17 unless the table says they hit it.
+    #[test]
+    fn standard_dealer_follows_soft_17_rule() {
+        let mut rules = blackjack::TableRules {
+            dealer_hits_soft_17: false,
+            ..Default::default()
+        };
+
+        // Standing on soft 17 (S17).
+        check_action_from_cards::<StandardDealer>((1, 6), &rules, actors::Action::Stand);
+        check_action_from_cards::<StandardDealer>((10, 7), &rules, actors::Action::Stand);
+
+        // Hitting soft 17 (H17), but still standing on hard 17 and soft 18.
+        rules.dealer_hits_soft_17 = true;
+        check_action_from_cards::<StandardDealer>((1, 6), &rules, actors::Action::Hit);
+        check_action_from_cards::<StandardDealer>((10, 7), &rules, actors::Action::Stand);
+        check_action_from_cards::<StandardDealer>((1, 7), &rules, actors::Action::Stand);
+        check_action_from_cards::<StandardDealer>((4, 8), &rules, actors::Action::Hit);
+    }
+
+    /// The upcard is the card players can see, with or without a hole card.
+    #[test]
+    fn standard_dealer_shows_upcard() {
+        let mut dealer = StandardDealer::new();
+        dealer.recieve_card(actor_tests::create_card_from_value(9));
+        assert_eq!(actor_tests::create_card_from_value(9), *dealer.upcard());
+
+        dealer.recieve_card(actor_tests::create_card_from_value(1));
+        assert_eq!(actor_tests::create_card_from_value(1), *dealer.upcard());
     }
 }
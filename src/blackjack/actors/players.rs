@@ -4,14 +4,49 @@
 //! act within the allowed moves in Blackjack.
 
 pub mod auto_player;
-pub mod human_player;
+pub mod chart_player;
+pub mod counting_player;
+pub mod scripted_player;
 
 pub use auto_player::AutoPlayer;
-pub use human_player::HumanPlayer;
+pub use chart_player::{BasicStrategyPlayer, ChartPlayer};
+pub use counting_player::CountingPlayer;
+pub use scripted_player::ScriptedPlayer;
+
+use std::cmp;
 
 use crate::blackjack::{self, actors};
 use crate::cards;
 
+/// One hand a player is playing in a round, along with the bet riding on it.
+/// Players start each round with a single hand, and splitting a pair gives them another.
+#[derive(Debug, Clone, Default)]
+pub struct PlayerHand {
+    /// The cards in this hand.
+    pub cards: cards::Hand,
+    /// How much money is bet on this hand (none if betting is disabled).
+    pub bet: Option<u32>,
+    /// Whether this hand was made by splitting a pair. Split hands can't be naturals.
+    pub from_split: bool,
+    /// Whether the player doubled down on this hand.
+    pub doubled: bool,
+    /// How much the player put up as insurance against the dealer having blackjack.
+    pub insurance: Option<u32>,
+    /// Whether the player took even money on their natural instead of risking a stand-off.
+    pub took_even_money: bool,
+    /// Whether the player surrendered this hand, giving up half the bet.
+    pub surrendered: bool,
+    /// Whether the player is done taking actions on this hand.
+    pub finished: bool,
+}
+
+impl PlayerHand {
+    /// Returns true if this hand is a natural (two cards worth 21 that didn't come from a split).
+    pub fn is_natural(&self) -> bool {
+        !self.from_split && blackjack::hand_is_natural(&self.cards)
+    }
+}
+
 /// A trait representing behavior every player in a game of blackjack should be able to handle.
 pub trait Player: actors::Actor {
     /// Creates a new object that implements Player.
@@ -25,91 +60,405 @@ pub trait Player: actors::Actor {
     /// Gets the total money a player has currently.
     fn money_mut(&mut self) -> &mut Option<u32>;
 
-    /// Gets how much money a player is betting on the current round.
-    fn bet_mut(&mut self) -> &mut Option<u32>;
+    /// Gets the total money a player has currently, without needing to borrow them mutably.
+    fn money(&self) -> Option<u32>;
+
+    /// Get a mutable reference to every hand the player is playing this round.
+    fn hands_mut(&mut self) -> &mut Vec<PlayerHand>;
+
+    /// Get a slice of every hand the player is playing this round.
+    fn hands(&self) -> &[PlayerHand];
+
+    /// Gets the index of the hand the player is currently acting on: the first one
+    /// they haven't finished, or the last hand if they've finished them all.
+    fn active_hand_index(&self) -> usize {
+        let hands = self.hands();
+        hands
+            .iter()
+            .position(|hand| !hand.finished)
+            .unwrap_or(hands.len() - 1)
+    }
+
+    /// Marks the hand the player is currently acting on as finished.
+    fn finish_hand(&mut self) {
+        let active = self.active_hand_index();
+        self.hands_mut()[active].finished = true;
+    }
+
+    /// Returns true once the player is done acting on every one of their hands.
+    fn finished_all_hands(&self) -> bool {
+        self.hands().iter().all(|hand| hand.finished)
+    }
+
+    /// Discards every hand the player has into the shoe's discard tray, leaving them with a
+    /// single empty hand for the next round.
+    fn discard_hands(&mut self, shoe: &mut cards::Shoe) {
+        let hands = self.hands_mut();
+        shoe.discard(hands.drain(..).flat_map(|hand| hand.cards));
+        hands.push(PlayerHand::default());
+    }
+
+    /// Gets how much money a player is betting on the hand they are currently playing.
+    fn bet_mut(&mut self) -> &mut Option<u32> {
+        let active = self.active_hand_index();
+        &mut self.hands_mut()[active].bet
+    }
 
     /// Solicits how much a player wants to bet and puts that money aside for betting.
-    fn place_bet(&mut self);
+    /// The bet has to be within the table's limits.
+    fn place_bet(&mut self, rules: &blackjack::TableRules);
 
-    /// Gives the player more money if they are out of it to keep the game going.  
-    fn buy_in_if_broke(&mut self, buy_in_amount: u32) {
-        if *self.money_mut() == Some(0) {
-            println!(
-                "You went broke, {}! Don't worry, I'll spot you some cash.",
-                self.name()
-            );
-            *self.money_mut() = Some(buy_in_amount);
+    /// Bets the table minimum, if the player has the money for it. Bots that always bet the
+    /// same amount place their bets this way.
+    fn place_minimum_bet(&mut self, rules: &blackjack::TableRules) {
+        let bet = rules.min_bet.max(1);
+        match self.money() {
+            Some(funds) if funds >= bet && rules.check_bet(bet).is_ok() => {
+                *self.bet_mut() = Some(bet);
+                *self.money_mut() = Some(funds - bet);
+            }
+            _ => {}
         }
     }
 
-    /// Get what action a player should take.
-    fn decide_action(&self, dealer_upcard: &cards::Card) -> actors::Action;
+    /// Gives the player more money if they are out of it to keep the game going. Players who
+    /// have some left but can't cover the table minimum count as broke too, since they could
+    /// never bet again, so the buy-in gets added to what they have.
+    /// Returns true if they needed it.
+    fn buy_in_if_broke(&mut self, buy_in_amount: u32, rules: &blackjack::TableRules) -> bool {
+        match *self.money_mut() {
+            Some(funds) if funds < rules.min_bet.max(1) => {
+                *self.money_mut() = Some(funds + buy_in_amount);
+                true
+            }
+            _ => false,
+        }
+    }
 
-    /// Carry out a player's actions in the game.
-    /// Returns true or false if they can take another turn or not.
-    fn handle_player_action(&mut self, action: actors::Action, deck: &mut cards::Deck) -> bool {
-        match action {
-            actors::Action::Hit => {
-                let deal = deck.pop().unwrap();
-                println!("Hit! NEW CARD: {}", deal);
-                self.recieve_card(deal);
-                false
+    /// When the dealer shows an ace, asks how much the player wants to put up as insurance,
+    /// up to `max_insurance` (half their bet). Insurance pays 2:1 if the dealer has blackjack.
+    /// Players decline insurance by default.
+    fn decide_insurance(&self, _max_insurance: u32) -> u32 {
+        0
+    }
+
+    /// When the dealer shows an ace and the player has a natural, asks if they want even money:
+    /// being paid 1:1 right away instead of risking a stand-off. Players decline by default.
+    fn decide_even_money(&self) -> bool {
+        false
+    }
+
+    /// Offers the player insurance (or even money if they have a natural) and puts any
+    /// insurance bet they make aside from their money.
+    fn offer_insurance(&mut self) {
+        let hand = &self.hands()[0];
+        let bet = match hand.bet {
+            Some(bet) if !hand.surrendered => bet,
+            _ => return,
+        };
+
+        if hand.is_natural() {
+            if self.decide_even_money() {
+                let hand = &mut self.hands_mut()[0];
+                hand.took_even_money = true;
+                hand.finished = true;
             }
-            actors::Action::Stand => true,
+            return;
+        }
+
+        let max_insurance = cmp::min(bet / 2, self.money().unwrap_or(0));
+        if max_insurance == 0 {
+            return;
+        }
+
+        let insurance = cmp::min(self.decide_insurance(max_insurance), max_insurance);
+        if insurance > 0 {
+            let money = self.money_mut().unwrap();
+            *self.money_mut() = Some(money - insurance);
+            self.hands_mut()[0].insurance = Some(insurance);
+        }
+    }
+
+    /// Pays out or takes the player's insurance bet once the dealer has checked for blackjack.
+    /// Returns how much insurance the player had put up, if any.
+    fn settle_insurance(&mut self, dealer_has_natural: bool) -> Option<u32> {
+        let insurance = self.hands_mut()[0].insurance.take()?;
+
+        if dealer_has_natural {
+            let winnings = insurance * 3;
+            *self.money_mut() = Some(self.money_mut().unwrap() + winnings);
+        }
+        Some(insurance)
+    }
+
+    /// Get what action a player should take. The action has to be one of the allowed actions
+    /// (which always includes hitting and standing), otherwise they will be asked again.
+    fn decide_action(
+        &self,
+        dealer_upcard: &cards::Card,
+        allowed_actions: &[actors::Action],
+    ) -> actors::Action;
+
+    /// Sees something that happened at the table. Players are shown everything the front end
+    /// is, including the cards dealt to every other seat and the dealer's hole card once it's
+    /// turned over, so they can keep track of what's been played. Players ignore it by default.
+    fn observe(&mut self, _event: &blackjack::Event) {}
+
+    /// Gets every action the player is allowed to take on the hand they are currently playing.
+    fn allowed_actions(&self, rules: &blackjack::TableRules) -> Vec<actors::Action> {
+        let mut actions = vec![actors::Action::Stand];
+        if self.can_hit(rules) {
+            actions.insert(0, actors::Action::Hit);
+        }
+        if self.can_double_down(rules) {
+            actions.push(actors::Action::DoubleDown);
+        }
+        if self.can_split(rules) {
+            actions.push(actors::Action::Split);
+        }
+        if self.can_surrender(rules) {
+            actions.push(actors::Action::Surrender);
         }
+        actions
+    }
+
+    /// Returns true if the player is allowed to hit the hand they are currently playing. Split
+    /// aces can't be hit unless the table allows it (they're only left open to be resplit).
+    fn can_hit(&self, rules: &blackjack::TableRules) -> bool {
+        let hand = &self.hands()[self.active_hand_index()];
+        !(hand.from_split && hand.cards[0].rank == cards::Rank::Ace && !rules.hit_split_aces)
+    }
+
+    /// Returns true if the player is allowed to surrender the hand they are currently playing.
+    /// The table has to allow it, and it can only be done on the first two cards before splitting.
+    fn can_surrender(&self, rules: &blackjack::TableRules) -> bool {
+        let hands = self.hands();
+        let hand = &hands[self.active_hand_index()];
+
+        rules.surrender_rule != blackjack::SurrenderRule::NoSurrender
+            && hands.len() == 1
+            && hand.cards.len() == 2
+            && !hand.is_natural()
     }
 
-    /// Decide what action to take and handle that action. Returns true if they can take another turn.
-    fn take_turn(&mut self, deck: &mut cards::Deck, dealer_upcard: &cards::Card) -> bool {
-        let action = self.decide_action(dealer_upcard);
-        self.handle_player_action(action, deck)
+    /// When the table allows early surrender and the dealer might have blackjack, asks if the player
+    /// wants to surrender before the dealer checks. By default players surrender early if they
+    /// would choose to surrender on their turn.
+    fn decide_early_surrender(&self, dealer_upcard: &cards::Card) -> bool {
+        let allowed_actions = [
+            actors::Action::Hit,
+            actors::Action::Stand,
+            actors::Action::Surrender,
+        ];
+        self.decide_action(dealer_upcard, &allowed_actions) == actors::Action::Surrender
     }
 
-    /// Handles the result for a player at the end of a round (showing it to the user, updating bet/money).
-    fn handle_round_result(&mut self, result: blackjack::PlayerRoundResult, payout_ratio: f64) {
-        print!("{}: {} ", self.name(), result);
-        if self.bet_mut().is_none() {
-            println!("");
+    /// Offers the player early surrender, surrendering their hand if they take it.
+    fn offer_early_surrender(&mut self, dealer_upcard: &cards::Card) {
+        if self.hands()[0].is_natural() {
             return;
         }
 
-        let bet = self.bet_mut().unwrap();
-        match result {
-            blackjack::PlayerRoundResult::Natural => {
-                let winnings = bet + (payout_ratio * bet as f64).floor() as u32;
-                *self.money_mut() = Some(self.money_mut().unwrap() + winnings);
-                println!(
-                    "You won ${}. (Total cash: ${})",
-                    winnings,
-                    self.money_mut().unwrap()
-                );
+        if self.decide_early_surrender(dealer_upcard) {
+            self.surrender();
+        }
+    }
+
+    /// Surrenders the hand the player is currently playing, finishing it.
+    fn surrender(&mut self) {
+        let active = self.active_hand_index();
+        let hand = &mut self.hands_mut()[active];
+        hand.surrendered = true;
+        hand.finished = true;
+    }
+
+    /// Returns true if the player is allowed to double down on the hand they are currently playing:
+    /// it has to be the hand's first two cards, the table's rules have to allow doubling on
+    /// that hand, and they need enough money to match the bet on it.
+    fn can_double_down(&self, rules: &blackjack::TableRules) -> bool {
+        let hand = &self.hands()[self.active_hand_index()];
+
+        if hand.cards.len() != 2 || (hand.from_split && !rules.double_after_split) {
+            return false;
+        }
+
+        if !self.can_hit(rules) {
+            return false;
+        }
+
+        if !rules
+            .double_down_rule
+            .allows(blackjack::hand_value(&hand.cards))
+        {
+            return false;
+        }
+
+        match hand.bet {
+            Some(bet) => self.money().unwrap_or(0) >= bet,
+            None => true,
+        }
+    }
+
+    /// Doubles the bet on the hand the player is currently playing and deals it one last card.
+    fn double_down(&mut self, shoe: &mut cards::Shoe) -> Result<(), &'static str> {
+        let active = self.active_hand_index();
+        let deal = shoe.draw()?;
+
+        if let Some(bet) = self.hands()[active].bet {
+            let money = self.money_mut().unwrap();
+            *self.money_mut() = Some(money - bet);
+            self.hands_mut()[active].bet = Some(bet + bet);
+        }
+
+        let hand = &mut self.hands_mut()[active];
+        hand.cards.push(deal);
+        hand.doubled = true;
+        Ok(())
+    }
+
+    /// Returns true if the player is allowed to split the hand they are currently playing:
+    /// it has to be a pair, they can't be at the table's limit of hands, and they need
+    /// enough money to match the bet on it.
+    fn can_split(&self, rules: &blackjack::TableRules) -> bool {
+        let hands = self.hands();
+        let hand = &hands[self.active_hand_index()];
+
+        if !blackjack::hand_is_pair(&hand.cards) || hands.len() >= rules.max_split_hands as usize {
+            return false;
+        }
+
+        if hand.from_split && hand.cards[0].rank == cards::Rank::Ace && !rules.resplit_aces {
+            return false;
+        }
+
+        match hand.bet {
+            Some(bet) => self.money().unwrap_or(0) >= bet,
+            None => true,
+        }
+    }
+
+    /// Splits the pair in the hand the player is currently playing into two hands, each with
+    /// the same bet, and deals a second card to both of them.
+    fn split_hand(
+        &mut self,
+        shoe: &mut cards::Shoe,
+        rules: &blackjack::TableRules,
+    ) -> Result<(), &'static str> {
+        if !shoe.can_draw(2) {
+            return Err("There aren't enough cards left to split.");
+        }
+        let active = self.active_hand_index();
+        let bet = self.hands()[active].bet;
+
+        if let Some(bet) = bet {
+            let money = self.money_mut().unwrap();
+            *self.money_mut() = Some(money - bet);
+        }
+
+        let hands = self.hands_mut();
+        let split_card = hands[active].cards.pop().unwrap();
+        let splitting_aces = split_card.rank == cards::Rank::Ace;
+        let mut new_hand = PlayerHand {
+            cards: vec![split_card],
+            bet,
+            from_split: true,
+            ..PlayerHand::default()
+        };
+
+        let hand = &mut hands[active];
+        hand.from_split = true;
+        hand.cards.push(shoe.draw()?);
+        new_hand.cards.push(shoe.draw()?);
+
+        // Split aces usually only get the one card each, unless they get another ace that
+        // can be resplit.
+        if splitting_aces && !rules.hit_split_aces {
+            let resplittable =
+                |cards: &[cards::Card]| rules.resplit_aces && blackjack::hand_is_pair(cards);
+            hand.finished = !resplittable(&hand.cards);
+            new_hand.finished = !resplittable(&new_hand.cards);
+        }
+
+        hands.insert(active + 1, new_hand);
+        Ok(())
+    }
+
+    /// Carry out a player's actions in the game.
+    /// Returns true if they are finished with the hand they are playing, false if they can keep acting on it.
+    /// If the action isn't allowed on the hand, returns an error and nothing happens.
+    fn handle_player_action(
+        &mut self,
+        action: actors::Action,
+        shoe: &mut cards::Shoe,
+        rules: &blackjack::TableRules,
+    ) -> Result<bool, &'static str> {
+        match action {
+            actors::Action::Hit => {
+                if !self.can_hit(rules) {
+                    return Err("You can't hit split aces.");
+                }
+                let deal = shoe.draw()?;
+                self.recieve_card(deal);
+                Ok(false)
             }
-            blackjack::PlayerRoundResult::Win => {
-                let winnings: u32 = bet + bet;
-                *self.money_mut() = Some(self.money_mut().unwrap() + winnings);
-                println!(
-                    "You won ${}. (Total cash: ${})",
-                    winnings,
-                    self.money_mut().unwrap()
-                );
+            actors::Action::Stand => Ok(true),
+            actors::Action::DoubleDown => {
+                if !self.can_double_down(rules) {
+                    return Err("You can't double down on this hand.");
+                }
+                self.double_down(shoe)?;
+                Ok(true)
             }
-            blackjack::PlayerRoundResult::Standoff => {
-                *self.money_mut() = Some(self.money_mut().unwrap() + bet);
-                println!(
-                    "You kept your original ${} bet (Total cash: ${})",
-                    bet,
-                    self.money_mut().unwrap()
-                );
+            actors::Action::Split => {
+                if !self.can_split(rules) {
+                    return Err("You can't split this hand.");
+                }
+                self.split_hand(shoe, rules)?;
+                Ok(false)
             }
-            blackjack::PlayerRoundResult::Lose => {
-                println!(
-                    "You lost your ${} bet. (Total cash: ${})",
-                    bet,
-                    self.money_mut().unwrap()
-                );
+            actors::Action::Surrender => {
+                if !self.can_surrender(rules) {
+                    return Err("You can't surrender this hand.");
+                }
+                self.surrender();
+                Ok(true)
             }
         }
-        *self.bet_mut() = None;
+    }
+
+    /// Handles the results for a player at the end of a round, paying out (or taking) the bet on each hand.
+    /// There is one result for each hand the player played, and returns how much money each hand paid back.
+    fn handle_round_result(
+        &mut self,
+        results: &[blackjack::PlayerRoundResult],
+        payout_ratio: f64,
+    ) -> Vec<u32> {
+        let mut payouts = Vec::new();
+
+        for (index, &result) in results.iter().enumerate() {
+            let bet = match self.hands_mut()[index].bet.take() {
+                Some(bet) => bet,
+                None => {
+                    payouts.push(0);
+                    continue;
+                }
+            };
+
+            let winnings = match result {
+                blackjack::PlayerRoundResult::Natural => {
+                    bet + (payout_ratio * bet as f64).floor() as u32
+                }
+                blackjack::PlayerRoundResult::Win | blackjack::PlayerRoundResult::EvenMoney => {
+                    bet + bet
+                }
+                blackjack::PlayerRoundResult::Standoff => bet,
+                blackjack::PlayerRoundResult::Surrender => bet / 2,
+                blackjack::PlayerRoundResult::Lose => 0,
+            };
+            *self.money_mut() = Some(self.money_mut().unwrap() + winnings);
+            payouts.push(winnings);
+        }
+        payouts
     }
 }
 
@@ -118,6 +467,7 @@ pub mod tests {
     use super::*;
     use crate::blackjack::actors;
     use crate::blackjack::actors::tests as actor_tests;
+    use crate::blackjack::actors::Actor;
 
     /// Helper function for checking player actions given their cards and what they can see from the dealer.
     pub fn check_action_from_cards<T: Player>(
@@ -129,6 +479,287 @@ pub mod tests {
         let mut player = T::new(0);
         player.recieve_card(actor_tests::create_card_from_value(card_values.0));
         player.recieve_card(actor_tests::create_card_from_value(card_values.1));
-        assert_eq!(player.decide_action(&upcard), action);
+        let allowed_actions = player.allowed_actions(&blackjack::TableRules::default());
+        assert_eq!(player.decide_action(&upcard, &allowed_actions), action);
+    }
+
+    /// Helper function for creating a player holding the given cards with a bet on their hand.
+    fn player_with_bet(card_values: (u32, u32), bet: u32) -> AutoPlayer {
+        let mut player = AutoPlayer::new(100);
+        *player.money_mut() = Some(100 - bet);
+        *player.bet_mut() = Some(bet);
+        player.recieve_card(actor_tests::create_card_from_value(card_values.0));
+        player.recieve_card(actor_tests::create_card_from_value(card_values.1));
+        player
+    }
+
+    #[test]
+    fn splits_pair_into_two_hands() {
+        let rules = blackjack::TableRules::default();
+        let mut shoe = cards::Shoe::stacked(vec![
+            actor_tests::create_card_from_value(3),
+            actor_tests::create_card_from_value(2),
+        ]);
+        let mut player = player_with_bet((8, 8), 10);
+
+        assert!(player.can_split(&rules));
+        assert!(!player
+            .handle_player_action(actors::Action::Split, &mut shoe, &rules)
+            .unwrap());
+
+        let hands = player.hands();
+        assert_eq!(2, hands.len());
+        assert_eq!(10, blackjack::hand_value(&hands[0].cards));
+        assert_eq!(11, blackjack::hand_value(&hands[1].cards));
+        assert!(hands
+            .iter()
+            .all(|hand| hand.bet == Some(10) && !hand.finished));
+        assert_eq!(Some(80), player.money());
+        assert!(shoe.is_empty());
+    }
+
+    #[test]
+    fn split_aces_get_one_card() {
+        let mut rules = blackjack::TableRules::default();
+        let split_aces = |rules: &blackjack::TableRules| {
+            // The shoe's top card is its last, so the first hand gets a ten and the second an ace.
+            let mut shoe = cards::Shoe::stacked(vec![
+                actor_tests::create_card_from_value(1),
+                actor_tests::create_card_from_value(10),
+            ]);
+            let mut player = player_with_bet((1, 1), 10);
+            player
+                .handle_player_action(actors::Action::Split, &mut shoe, rules)
+                .unwrap();
+            player
+        };
+
+        // The second hand got another ace, but aces can't be resplit unless the table allows it.
+        let player = split_aces(&rules);
+        assert!(player.finished_all_hands());
+        // A split hand of 21 is not a natural.
+        assert!(!player.hands()[0].is_natural());
+
+        rules.resplit_aces = true;
+        let mut player = split_aces(&rules);
+        assert_eq!(1, player.active_hand_index());
+        assert_eq!(
+            vec![actors::Action::Stand, actors::Action::Split],
+            player.allowed_actions(&rules)
+        );
+        let mut shoe = cards::Shoe::stacked(vec![
+            actor_tests::create_card_from_value(9),
+            actor_tests::create_card_from_value(8),
+        ]);
+        assert!(player
+            .handle_player_action(actors::Action::Hit, &mut shoe, &rules)
+            .is_err());
+        player
+            .handle_player_action(actors::Action::Split, &mut shoe, &rules)
+            .unwrap();
+        assert_eq!(3, player.hands().len());
+        assert!(player.finished_all_hands());
+    }
+
+    #[test]
+    fn cannot_split_without_pair_money_or_room() {
+        let mut rules = blackjack::TableRules::default();
+
+        assert!(!player_with_bet((8, 9), 10).can_split(&rules));
+        assert!(!player_with_bet((8, 8), 60).can_split(&rules));
+        assert!(player_with_bet((10, 10), 50).can_split(&rules));
+
+        rules.max_split_hands = 1;
+        assert!(!player_with_bet((8, 8), 10).can_split(&rules));
+    }
+
+    #[test]
+    fn doubles_bet_and_takes_one_card() {
+        let rules = blackjack::TableRules::default();
+        let mut shoe = cards::Shoe::stacked(vec![actor_tests::create_card_from_value(10)]);
+        let mut player = player_with_bet((5, 6), 10);
+
+        assert!(player
+            .allowed_actions(&rules)
+            .contains(&actors::Action::DoubleDown));
+        assert!(player
+            .handle_player_action(actors::Action::DoubleDown, &mut shoe, &rules)
+            .unwrap());
+
+        let hand = &player.hands()[0];
+        assert_eq!(21, blackjack::hand_value(&hand.cards));
+        assert_eq!(Some(20), hand.bet);
+        assert!(hand.doubled);
+        assert_eq!(Some(80), player.money());
+    }
+
+    #[test]
+    fn double_down_follows_table_rules() {
+        let mut rules = blackjack::TableRules::default();
+        assert!(player_with_bet((10, 7), 10).can_double_down(&rules));
+        assert!(!player_with_bet((10, 7), 60).can_double_down(&rules));
+
+        rules.double_down_rule = blackjack::DoubleDownRule::TenToEleven;
+        assert!(!player_with_bet((10, 7), 10).can_double_down(&rules));
+        assert!(!player_with_bet((5, 4), 10).can_double_down(&rules));
+        assert!(player_with_bet((5, 5), 10).can_double_down(&rules));
+
+        // Doubling after a split depends on the table too.
+        let mut split_player = player_with_bet((5, 5), 10);
+        split_player.hands_mut()[0].from_split = true;
+        assert!(split_player.can_double_down(&rules));
+        rules.double_after_split = false;
+        assert!(!split_player.can_double_down(&rules));
+
+        // Can only double down on the first two cards.
+        let mut three_cards = player_with_bet((2, 3), 10);
+        three_cards.recieve_card(actor_tests::create_card_from_value(5));
+        assert!(!three_cards.can_double_down(&rules));
+    }
+
+    /// A player who always takes as much insurance as they can, and takes even money.
+    struct InsuredPlayer(AutoPlayer);
+
+    impl Actor for InsuredPlayer {
+        fn hand_mut(&mut self) -> &mut cards::Hand {
+            self.0.hand_mut()
+        }
+
+        fn hand(&self) -> &[cards::Card] {
+            self.0.hand()
+        }
+    }
+
+    impl Player for InsuredPlayer {
+        fn new(buy_in: u32) -> Self {
+            InsuredPlayer(AutoPlayer::new(buy_in))
+        }
+
+        fn name(&self) -> &str {
+            "Insured"
+        }
+
+        fn money_mut(&mut self) -> &mut Option<u32> {
+            self.0.money_mut()
+        }
+
+        fn money(&self) -> Option<u32> {
+            self.0.money()
+        }
+
+        fn hands_mut(&mut self) -> &mut Vec<PlayerHand> {
+            self.0.hands_mut()
+        }
+
+        fn hands(&self) -> &[PlayerHand] {
+            self.0.hands()
+        }
+
+        fn place_bet(&mut self, _rules: &blackjack::TableRules) {}
+
+        fn decide_insurance(&self, max_insurance: u32) -> u32 {
+            max_insurance
+        }
+
+        fn decide_even_money(&self) -> bool {
+            true
+        }
+
+        fn decide_action(
+            &self,
+            dealer_upcard: &cards::Card,
+            allowed_actions: &[actors::Action],
+        ) -> actors::Action {
+            self.0.decide_action(dealer_upcard, allowed_actions)
+        }
+    }
+
+    #[test]
+    fn insurance_pays_two_to_one() {
+        let mut player = InsuredPlayer::new(100);
+        *player.money_mut() = Some(80);
+        *player.bet_mut() = Some(20);
+        player.recieve_card(actor_tests::create_card_from_value(10));
+        player.recieve_card(actor_tests::create_card_from_value(7));
+
+        player.offer_insurance();
+        assert_eq!(Some(10), player.hands()[0].insurance);
+        assert_eq!(Some(70), player.money());
+
+        player.settle_insurance(true);
+        assert_eq!(None, player.hands()[0].insurance);
+        assert_eq!(Some(100), player.money());
+
+        // Losing insurance just leaves the money gone.
+        player.offer_insurance();
+        player.settle_insurance(false);
+        assert_eq!(Some(90), player.money());
+    }
+
+    #[test]
+    fn even_money_finishes_natural() {
+        let mut player = InsuredPlayer::new(100);
+        *player.money_mut() = Some(80);
+        *player.bet_mut() = Some(20);
+        player.recieve_card(actor_tests::create_card_from_value(1));
+        player.recieve_card(actor_tests::create_card_from_value(10));
+
+        player.offer_insurance();
+        let hand = &player.hands()[0];
+        assert!(hand.took_even_money && hand.finished);
+        assert_eq!(None, hand.insurance);
+
+        player.handle_round_result(&[blackjack::PlayerRoundResult::EvenMoney], 1.5);
+        assert_eq!(Some(120), player.money());
+    }
+
+    #[test]
+    fn buys_in_once_the_table_minimum_is_out_of_reach() {
+        let rules = blackjack::TableRules {
+            min_bet: 25,
+            ..Default::default()
+        };
+        let mut player = AutoPlayer::new(100);
+        assert!(!player.buy_in_if_broke(500, &rules));
+        assert_eq!(Some(100), player.money());
+
+        // Keeping some money doesn't help if it's less than the minimum bet.
+        *player.money_mut() = Some(10);
+        assert!(player.buy_in_if_broke(500, &rules));
+        assert_eq!(Some(510), player.money());
+
+        *player.money_mut() = Some(0);
+        assert!(player.buy_in_if_broke(500, &rules));
+        assert_eq!(Some(500), player.money());
+
+        // Players who aren't betting never need to buy in.
+        let mut player = AutoPlayer::new(0);
+        assert!(!player.buy_in_if_broke(500, &rules));
+        assert_eq!(None, player.money());
+    }
+
+    #[test]
+    fn surrender_refunds_half_bet() {
+        let mut rules = blackjack::TableRules::default();
+        let mut shoe = cards::Shoe::stacked(Vec::new());
+        let mut player = player_with_bet((10, 6), 20);
+
+        assert!(!player.can_surrender(&rules));
+        rules.surrender_rule = blackjack::SurrenderRule::Late;
+        assert!(player
+            .allowed_actions(&rules)
+            .contains(&actors::Action::Surrender));
+        assert!(player
+            .handle_player_action(actors::Action::Surrender, &mut shoe, &rules)
+            .unwrap());
+        assert!(player.hands()[0].surrendered);
+
+        player.handle_round_result(&[blackjack::PlayerRoundResult::Surrender], 1.5);
+        assert_eq!(Some(90), player.money());
+
+        // Can't surrender once a hand has been split.
+        let mut split_player = player_with_bet((8, 8), 20);
+        split_player.hands_mut().push(PlayerHand::default());
+        assert!(!split_player.can_surrender(&rules));
     }
 }
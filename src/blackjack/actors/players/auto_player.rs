@@ -7,26 +7,18 @@ use crate::cards;
 /// A simple bot acting as a player that will always do the most optimal move
 /// given their hand without counting cards.
 pub struct AutoPlayer {
-    hand: cards::Hand,
+    hands: Vec<players::PlayerHand>,
     money: Option<u32>,
-    bet: Option<u32>,
 }
 
 impl actors::Actor for AutoPlayer {
-    fn hand_mut(&mut self) -> &mut Vec<cards::Card> {
-        &mut self.hand
+    fn hand_mut(&mut self) -> &mut cards::Hand {
+        let active = self.active_hand_index();
+        &mut self.hands[active].cards
     }
 
     fn hand(&self) -> &[cards::Card] {
-        self.hand.as_slice()
-    }
-
-    fn show_hand(&self) {
-        print!("{}'s Cards: {}", self.name(), &self.hand[0]);
-        for card in &self.hand[1..] {
-            print!(", {}", card);
-        }
-        println!("     (value: {})", blackjack::hand_value(&self.hand[..]));
+        self.hands[self.active_hand_index()].cards.as_slice()
     }
 }
 
@@ -35,9 +27,8 @@ impl players::Player for AutoPlayer {
         let money = if buy_in > 0 { Some(buy_in) } else { None };
 
         AutoPlayer {
-            hand: Vec::new(),
+            hands: vec![players::PlayerHand::default()],
             money,
-            bet: None,
         }
     }
 
@@ -49,16 +40,28 @@ impl players::Player for AutoPlayer {
         &mut self.money
     }
 
-    fn bet_mut(&mut self) -> &mut Option<u32> {
-        &mut self.bet
+    fn money(&self) -> Option<u32> {
+        self.money
+    }
+
+    fn hands_mut(&mut self) -> &mut Vec<players::PlayerHand> {
+        &mut self.hands
     }
 
-    fn place_bet(&mut self) {
+    fn hands(&self) -> &[players::PlayerHand] {
+        self.hands.as_slice()
+    }
+
+    fn place_bet(&mut self, _rules: &blackjack::TableRules) {
         // Maybe put in bot betting logic.
         //println!("Getting bet for {}", self.name());
     }
 
-    fn decide_action(&self, dealer_upcard: &cards::Card) -> actors::Action {
+    fn decide_action(
+        &self,
+        dealer_upcard: &cards::Card,
+        _allowed_actions: &[actors::Action],
+    ) -> actors::Action {
         // If the player has a soft hand, hit until at least 18.
         if blackjack::is_soft_hand(blackjack::raw_hand_value(self.hand()), self.hand()) {
             if blackjack::hand_value(self.hand()) >= 18 {
@@ -97,9 +100,8 @@ impl AutoPlayer {
     #[allow(dead_code)]
     fn new_default() -> AutoPlayer {
         AutoPlayer {
-            hand: Vec::new(),
+            hands: vec![players::PlayerHand::default()],
             money: None,
-            bet: None,
         }
     }
 }
@@ -142,4 +144,22 @@ mod tests {
         // If the dealer's card is fair, hit at 12 or less.
         players_tests::check_action_from_cards::<AutoPlayer>((10, 2), 2, actors::Action::Hit);
     }
+
+    #[test]
+    fn bot_declines_insurance() {
+        let mut bot = AutoPlayer::new(100);
+        *bot.bet_mut() = Some(10);
+        bot.recieve_card(actor_tests::create_card_from_value(10));
+        bot.recieve_card(actor_tests::create_card_from_value(6));
+
+        assert_eq!(0, bot.decide_insurance(5));
+        bot.offer_insurance();
+        assert_eq!(None, bot.hands()[0].insurance);
+
+        // Even with a natural, the bot would rather take its chances.
+        bot.discard_hands(&mut cards::Shoe::stacked(Vec::new()));
+        bot.recieve_card(actor_tests::create_card_from_value(1));
+        bot.recieve_card(actor_tests::create_card_from_value(10));
+        assert!(!bot.decide_even_money());
+    }
 }
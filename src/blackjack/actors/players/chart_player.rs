@@ -0,0 +1,217 @@
+use std::path::Path;
+
+use crate::blackjack::actors::players;
+use crate::blackjack::actors::players::Player;
+use crate::blackjack::actors::Actor;
+use crate::blackjack::strategy::{Chart, ChartError};
+use crate::blackjack::{self, actors};
+use crate::cards;
+
+/// A bot that plays exactly by a strategy chart. By default that's perfect basic strategy
+/// for the table's rules: the best way to play each hand without counting cards, including
+/// when to double down, split, and surrender. It can also play a chart loaded from a file,
+/// which is handy for trying out custom strategies, or strategies for rules the built-in
+/// charts don't cover.
+pub struct ChartPlayer {
+    name: String,
+    hands: Vec<players::PlayerHand>,
+    money: Option<u32>,
+    chart: Chart,
+}
+
+/// A [`ChartPlayer`] playing basic strategy, which is what [`ChartPlayer::basic_strategy`]
+/// and [`Player::new`] create.
+pub type BasicStrategyPlayer = ChartPlayer;
+
+impl ChartPlayer {
+    /// Creates a bot that plays basic strategy for the given table rules.
+    pub fn basic_strategy(buy_in: u32, rules: &blackjack::TableRules) -> ChartPlayer {
+        ChartPlayer::named("Basic Strategy Bot", buy_in, Chart::basic_strategy(rules))
+    }
+
+    /// Creates a bot that plays by the given chart.
+    pub fn with_chart(buy_in: u32, chart: Chart) -> ChartPlayer {
+        ChartPlayer::named("Chart Bot", buy_in, chart)
+    }
+
+    /// Creates a bot that plays by the chart in a CSV or TOML file. Fails if the file can't
+    /// be read or the chart is missing any cells.
+    pub fn from_file(buy_in: u32, path: &Path) -> Result<ChartPlayer, ChartError> {
+        Ok(ChartPlayer::with_chart(buy_in, Chart::from_file(path)?))
+    }
+
+    /// Gets the strategy chart the bot plays by.
+    pub fn chart(&self) -> &Chart {
+        &self.chart
+    }
+
+    fn named(name: &str, buy_in: u32, chart: Chart) -> ChartPlayer {
+        let money = if buy_in > 0 { Some(buy_in) } else { None };
+
+        ChartPlayer {
+            name: name.to_string(),
+            hands: vec![players::PlayerHand::default()],
+            money,
+            chart,
+        }
+    }
+}
+
+impl actors::Actor for ChartPlayer {
+    fn hand_mut(&mut self) -> &mut cards::Hand {
+        let active = self.active_hand_index();
+        &mut self.hands[active].cards
+    }
+
+    fn hand(&self) -> &[cards::Card] {
+        self.hands[self.active_hand_index()].cards.as_slice()
+    }
+}
+
+impl players::Player for ChartPlayer {
+    /// Creates a bot that plays basic strategy for the default table rules.
+    fn new(buy_in: u32) -> ChartPlayer {
+        ChartPlayer::basic_strategy(buy_in, &blackjack::TableRules::default())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn money_mut(&mut self) -> &mut Option<u32> {
+        &mut self.money
+    }
+
+    fn money(&self) -> Option<u32> {
+        self.money
+    }
+
+    fn hands_mut(&mut self) -> &mut Vec<players::PlayerHand> {
+        &mut self.hands
+    }
+
+    fn hands(&self) -> &[players::PlayerHand] {
+        self.hands.as_slice()
+    }
+
+    fn place_bet(&mut self, rules: &blackjack::TableRules) {
+        self.place_minimum_bet(rules);
+    }
+
+    fn decide_action(
+        &self,
+        dealer_upcard: &cards::Card,
+        allowed_actions: &[actors::Action],
+    ) -> actors::Action {
+        self.chart
+            .decide(self.hand(), dealer_upcard, allowed_actions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::actors::players::tests as players_tests;
+    use crate::blackjack::actors::tests as actor_tests;
+    use crate::blackjack::strategy::{Play, UPCARDS};
+
+    #[test]
+    fn chart_bot_adds_card_to_hand() {
+        actor_tests::adds_card_to_hand(ChartPlayer::new(0));
+    }
+
+    #[test]
+    fn basic_strategy_bot_acts_properly() {
+        // Double down on 11 against a 6.
+        players_tests::check_action_from_cards::<ChartPlayer>(
+            (5, 6),
+            6,
+            actors::Action::DoubleDown,
+        );
+
+        // Always split aces and eights.
+        players_tests::check_action_from_cards::<ChartPlayer>((1, 1), 10, actors::Action::Split);
+        players_tests::check_action_from_cards::<ChartPlayer>((8, 8), 1, actors::Action::Split);
+
+        // Stand on 12 against a 4, but hit it against a 2.
+        players_tests::check_action_from_cards::<ChartPlayer>((10, 2), 4, actors::Action::Stand);
+        players_tests::check_action_from_cards::<ChartPlayer>((10, 2), 2, actors::Action::Hit);
+
+        // Hit soft 18 against a 9.
+        players_tests::check_action_from_cards::<ChartPlayer>((1, 7), 9, actors::Action::Hit);
+    }
+
+    #[test]
+    fn basic_strategy_bot_plays_for_the_table_rules() {
+        let rules = blackjack::TableRules::vegas_strip();
+        let mut bot = ChartPlayer::basic_strategy(0, &rules);
+        assert_eq!("Basic Strategy Bot", bot.name());
+        bot.recieve_card(actor_tests::create_card_from_value(10));
+        bot.recieve_card(actor_tests::create_card_from_value(6));
+
+        let upcard = actor_tests::create_card_from_value(10);
+        let allowed_actions = bot.allowed_actions(&rules);
+        assert_eq!(
+            actors::Action::Surrender,
+            bot.decide_action(&upcard, &allowed_actions)
+        );
+        assert!(bot.decide_early_surrender(&upcard));
+    }
+
+    #[test]
+    fn basic_strategy_bot_bets_the_table_minimum() {
+        let rules = blackjack::TableRules {
+            min_bet: 25,
+            ..Default::default()
+        };
+        let mut bot = ChartPlayer::basic_strategy(40, &rules);
+        bot.place_bet(&rules);
+        assert_eq!(Some(25), bot.hands()[0].bet);
+        assert_eq!(Some(15), bot.money());
+
+        // Without enough money left for the minimum, it sits the round out.
+        bot.discard_hands(&mut cards::Shoe::stacked(Vec::new()));
+        bot.place_bet(&rules);
+        assert_eq!(None, bot.hands()[0].bet);
+        assert_eq!(Some(15), bot.money());
+
+        // Neither does a bot playing without money.
+        let mut bot = ChartPlayer::basic_strategy(0, &rules);
+        bot.place_bet(&rules);
+        assert_eq!(None, bot.hands()[0].bet);
+    }
+
+    #[test]
+    fn chart_bot_plays_its_chart_exactly() {
+        // Never bust: stand on every hard 12 or more, even against a ten.
+        let chart = Chart::new(
+            std::array::from_fn(|row| {
+                let play = if row as u32 + 4 >= 12 {
+                    Play::Stand
+                } else {
+                    Play::Hit
+                };
+                [play; UPCARDS]
+            }),
+            [[Play::Stand; UPCARDS]; 10],
+            [[Play::Split; UPCARDS]; 10],
+        );
+        let rules = blackjack::TableRules::default();
+        let mut bot = ChartPlayer::with_chart(0, chart);
+        assert_eq!("Chart Bot", bot.name());
+        bot.recieve_card(actor_tests::create_card_from_value(10));
+        bot.recieve_card(actor_tests::create_card_from_value(6));
+
+        let upcard = actor_tests::create_card_from_value(10);
+        assert_eq!(
+            actors::Action::Stand,
+            bot.decide_action(&upcard, &bot.allowed_actions(&rules))
+        );
+    }
+
+    #[test]
+    fn chart_bot_reports_bad_chart_files() {
+        let result = ChartPlayer::from_file(0, Path::new("no/such/chart.csv"));
+        assert!(matches!(result, Err(ChartError::Io(_))));
+    }
+}
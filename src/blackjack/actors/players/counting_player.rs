@@ -0,0 +1,328 @@
+use crate::blackjack::actors::players;
+use crate::blackjack::actors::players::Player;
+use crate::blackjack::actors::Actor;
+use crate::blackjack::counting::{CardCount, CountingSystem, HiLo};
+use crate::blackjack::strategy::{Chart, DeviationTable};
+use crate::blackjack::{self, actors};
+use crate::cards;
+
+/// The most units the card counter will bet, once the true count is that high.
+const MAX_BET_UNITS: f64 = 8.0;
+
+/// A bot that counts cards with any counting system (Hi-Lo unless another is picked),
+/// watching every card dealt at the table. It plays basic strategy, except where the count
+/// calls for one of its system's [index plays](CountingSystem::index_plays) or the
+/// deviations it was given, and raises its bet as the true count goes up, betting one table
+/// minimum for each point of true count (but always at least one, and at most eight).
+///
+/// Hi-Lo counters play the Fab 4 and Illustrious 18, which are worked out for games where the
+/// dealer stands on soft 17. Counters using other systems have no index plays of their own,
+/// so they stick to basic strategy unless given deviations.
+pub struct CountingPlayer<S: CountingSystem = HiLo> {
+    name: String,
+    hands: Vec<players::PlayerHand>,
+    money: Option<u32>,
+    chart: Chart,
+    deviations: DeviationTable,
+    count: CardCount<S>,
+}
+
+impl<S: CountingSystem> CountingPlayer<S> {
+    /// Creates a card counter that counts with the given system and plays basic strategy
+    /// for the given table rules, starting the count on a freshly shuffled shoe.
+    pub fn with_system(buy_in: u32, rules: &blackjack::TableRules, system: S) -> CountingPlayer<S> {
+        let money = if buy_in > 0 { Some(buy_in) } else { None };
+
+        CountingPlayer {
+            name: format!("{} Counter", system.name()),
+            hands: vec![players::PlayerHand::default()],
+            money,
+            chart: Chart::basic_strategy(rules),
+            deviations: system.index_plays(),
+            count: CardCount::new(system, rules.num_decks),
+        }
+    }
+
+    /// Plays the given index plays instead of the ones that come with its counting system.
+    pub fn with_deviations(mut self, deviations: DeviationTable) -> CountingPlayer<S> {
+        self.deviations = deviations;
+        self
+    }
+
+    /// Counts a shoe filled with the given deck instead of standard decks, so the true count
+    /// goes by how many of those decks are left.
+    pub fn with_deck(mut self, deck: &cards::DeckBuilder) -> CountingPlayer<S> {
+        self.count = self.count.with_deck_size(deck.deck_size());
+        self
+    }
+
+    /// Gets the count the bot has kept so far.
+    pub fn count(&self) -> &CardCount<S> {
+        &self.count
+    }
+
+    /// Works out how much to bet at the current count, within the table limits and the
+    /// money the bot has.
+    fn bet_for_count(&self, rules: &blackjack::TableRules, funds: u32) -> u32 {
+        let units = self.count.true_count().floor().clamp(1.0, MAX_BET_UNITS) as u32;
+        let mut bet = rules.min_bet.max(1) * units;
+        if let Some(max_bet) = rules.max_bet {
+            bet = bet.min(max_bet);
+        }
+        bet.min(funds)
+    }
+}
+
+impl<S: CountingSystem + Default> CountingPlayer<S> {
+    /// Creates a card counter that plays basic strategy for the given table rules, starting
+    /// the count on a freshly shuffled shoe.
+    pub fn for_rules(buy_in: u32, rules: &blackjack::TableRules) -> CountingPlayer<S> {
+        CountingPlayer::with_system(buy_in, rules, S::default())
+    }
+}
+
+impl<S: CountingSystem + Default> actors::Actor for CountingPlayer<S> {
+    fn hand_mut(&mut self) -> &mut cards::Hand {
+        let active = self.active_hand_index();
+        &mut self.hands[active].cards
+    }
+
+    fn hand(&self) -> &[cards::Card] {
+        self.hands[self.active_hand_index()].cards.as_slice()
+    }
+}
+
+impl<S: CountingSystem + Default> players::Player for CountingPlayer<S> {
+    /// Creates a card counter for the default table rules.
+    fn new(buy_in: u32) -> CountingPlayer<S> {
+        CountingPlayer::for_rules(buy_in, &blackjack::TableRules::default())
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn money_mut(&mut self) -> &mut Option<u32> {
+        &mut self.money
+    }
+
+    fn money(&self) -> Option<u32> {
+        self.money
+    }
+
+    fn hands_mut(&mut self) -> &mut Vec<players::PlayerHand> {
+        &mut self.hands
+    }
+
+    fn hands(&self) -> &[players::PlayerHand] {
+        self.hands.as_slice()
+    }
+
+    fn place_bet(&mut self, rules: &blackjack::TableRules) {
+        let funds = match self.money {
+            Some(funds) if funds > 0 => funds,
+            _ => return,
+        };
+
+        let bet = self.bet_for_count(rules, funds);
+        // Sit the round out rather than bet less than the table minimum.
+        if rules.check_bet(bet).is_err() {
+            return;
+        }
+        *self.bet_mut() = Some(bet);
+        self.money = Some(funds - bet);
+    }
+
+    fn decide_action(
+        &self,
+        dealer_upcard: &cards::Card,
+        allowed_actions: &[actors::Action],
+    ) -> actors::Action {
+        self.deviations.decide(
+            &self.chart,
+            self.hand(),
+            dealer_upcard,
+            allowed_actions,
+            self.count.true_count(),
+        )
+    }
+
+    fn decide_insurance(&self, max_insurance: u32) -> u32 {
+        if self.deviations.takes_insurance(self.count.true_count()) {
+            max_insurance
+        } else {
+            0
+        }
+    }
+
+    fn decide_even_money(&self) -> bool {
+        self.deviations.takes_insurance(self.count.true_count())
+    }
+
+    fn observe(&mut self, event: &blackjack::Event) {
+        self.count.observe(event);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::actors::players::tests as players_tests;
+    use crate::blackjack::actors::tests as actor_tests;
+    use crate::blackjack::counting::{CountStyle, Ko, WongHalves};
+    use crate::blackjack::{Event, Participant};
+
+    /// Shows the bot a card dealt to the dealer.
+    fn show_card<S: CountingSystem + Default>(bot: &mut CountingPlayer<S>, value: u32) {
+        bot.observe(&Event::CardDealt {
+            to: Participant::Dealer,
+            card: Some(actor_tests::create_card_from_value(value)),
+        });
+    }
+
+    #[test]
+    fn counting_bot_adds_card_to_hand() {
+        actor_tests::adds_card_to_hand(CountingPlayer::<HiLo>::new(0));
+    }
+
+    #[test]
+    fn counting_bot_plays_basic_strategy() {
+        players_tests::check_action_from_cards::<CountingPlayer>(
+            (5, 6),
+            6,
+            actors::Action::DoubleDown,
+        );
+        players_tests::check_action_from_cards::<CountingPlayer>((10, 6), 7, actors::Action::Hit);
+    }
+
+    #[test]
+    fn counting_bot_bets_more_as_the_count_goes_up() {
+        let rules = blackjack::TableRules {
+            num_decks: 1,
+            min_bet: 10,
+            max_bet: Some(50),
+            ..Default::default()
+        };
+        let mut bot = CountingPlayer::<HiLo>::for_rules(1000, &rules);
+        bot.place_bet(&rules);
+        assert_eq!(Some(10), bot.hands()[0].bet);
+        assert_eq!(Some(990), bot.money());
+
+        // Four low cards with most of a deck left is a true count of 4.
+        let mut bot = CountingPlayer::<HiLo>::for_rules(1000, &rules);
+        for value in [2, 3, 4, 5] {
+            show_card(&mut bot, value);
+        }
+        assert_eq!(4.0, bot.count().running_count());
+        bot.place_bet(&rules);
+        assert_eq!(Some(40), bot.hands()[0].bet);
+
+        // The bet never goes over the table maximum.
+        for value in [2, 3, 4, 5, 6, 6] {
+            show_card(&mut bot, value);
+        }
+        bot.discard_hands(&mut cards::Shoe::stacked(Vec::new()));
+        bot.place_bet(&rules);
+        assert_eq!(Some(50), bot.hands()[0].bet);
+    }
+
+    #[test]
+    fn counting_bot_plays_index_plays() {
+        let rules = blackjack::TableRules::default();
+        let ten = actor_tests::create_card_from_value(10);
+        let mut bot = CountingPlayer::<HiLo>::for_rules(0, &rules);
+        bot.hand_mut().push(actor_tests::create_card_from_value(10));
+        bot.hand_mut().push(actor_tests::create_card_from_value(6));
+        let allowed = [actors::Action::Hit, actors::Action::Stand];
+
+        // A ten and a six with a negative count is a hit, and the bot never takes insurance.
+        show_card(&mut bot, 10);
+        assert_eq!(actors::Action::Hit, bot.decide_action(&ten, &allowed));
+        assert_eq!(0, bot.decide_insurance(5));
+        assert!(!bot.decide_even_money());
+
+        // Once the count gets high enough it stands, and then takes insurance.
+        for value in [2, 3, 4, 5, 6, 2, 3, 4, 5, 6, 2, 3, 4, 5, 6, 2, 3, 4, 5, 6] {
+            show_card(&mut bot, value);
+        }
+        assert!(bot.count().true_count() >= 3.0);
+        assert_eq!(actors::Action::Stand, bot.decide_action(&ten, &allowed));
+        assert_eq!(5, bot.decide_insurance(5));
+        assert!(bot.decide_even_money());
+
+        // Without any deviations, it sticks to basic strategy.
+        let mut bot = bot.with_deviations(DeviationTable::new());
+        bot.observe(&Event::CardDealt {
+            to: Participant::Dealer,
+            card: None,
+        });
+        assert_eq!(actors::Action::Hit, bot.decide_action(&ten, &allowed));
+        assert_eq!(0, bot.decide_insurance(5));
+    }
+
+    #[test]
+    fn counting_bot_counts_decks_of_any_size() {
+        let rules = blackjack::TableRules::default();
+        let mut standard = CountingPlayer::<HiLo>::for_rules(0, &rules);
+        let mut spanish =
+            CountingPlayer::<HiLo>::for_rules(0, &rules).with_deck(&cards::DeckBuilder::spanish());
+        assert_eq!(6 * 48, spanish.count().cards_remaining());
+
+        for value in [2, 3, 4, 5] {
+            show_card(&mut standard, value);
+            show_card(&mut spanish, value);
+        }
+        // The same running count is worth more with fewer cards in each deck.
+        assert_eq!(4.0 / (308.0 / 52.0), standard.count().true_count());
+        assert_eq!(4.0 / (284.0 / 48.0), spanish.count().true_count());
+    }
+
+    #[test]
+    fn counting_bot_starts_over_after_a_reshuffle() {
+        let mut bot = CountingPlayer::<HiLo>::new(0);
+        show_card(&mut bot, 10);
+        show_card(&mut bot, 1);
+        assert_eq!(-2.0, bot.count().running_count());
+
+        bot.observe(&Event::ShoeReshuffled);
+        assert_eq!(0.0, bot.count().running_count());
+    }
+
+    #[test]
+    fn counting_bot_counts_with_its_system() {
+        let rules = blackjack::TableRules::default();
+        let mut halves = CountingPlayer::with_system(0, &rules, WongHalves);
+        let mut ko = CountingPlayer::<Ko>::for_rules(0, &rules);
+        assert_eq!("Wong Halves Counter", halves.name());
+        assert_eq!("KO Counter", ko.name());
+
+        for value in [2, 5, 7] {
+            show_card(&mut halves, value);
+            show_card(&mut ko, value);
+        }
+        assert_eq!(2.5, halves.count().running_count());
+        // KO starts six decks at -20.
+        assert_eq!(-17.0, ko.count().running_count());
+    }
+
+    #[test]
+    fn only_hi_lo_counters_get_the_standard_index_plays() {
+        let rules = blackjack::TableRules::default();
+        let hi_lo = CountingPlayer::<HiLo>::for_rules(0, &rules);
+        let mut zen = CountingPlayer::with_system(0, &rules, CountStyle::Zen);
+        assert!(!hi_lo.deviations.deviations().is_empty());
+        assert_eq!(DeviationTable::new(), zen.deviations);
+
+        // However high the count, a Zen counter plays basic strategy and skips insurance.
+        let ten = actor_tests::create_card_from_value(10);
+        zen.hand_mut().push(actor_tests::create_card_from_value(10));
+        zen.hand_mut().push(actor_tests::create_card_from_value(6));
+        for _ in 0..20 {
+            show_card(&mut zen, 5);
+        }
+        assert!(zen.count().true_count() >= 3.0);
+        let allowed = [actors::Action::Hit, actors::Action::Stand];
+        assert_eq!(actors::Action::Hit, zen.decide_action(&ten, &allowed));
+        assert_eq!(0, zen.decide_insurance(5));
+    }
+}
@@ -0,0 +1,110 @@
+use std::cell::Cell;
+
+use crate::blackjack::actors::players;
+use crate::blackjack::actors::players::Player;
+use crate::blackjack::{self, actors};
+use crate::cards;
+
+/// A player that takes a list of actions in order, no matter what cards they have.
+/// Once they run out of actions they stand. Handy for setting up exact situations in tests.
+pub struct ScriptedPlayer {
+    hands: Vec<players::PlayerHand>,
+    money: Option<u32>,
+    actions: Vec<actors::Action>,
+    /// How many of the actions have been taken so far.
+    next_action: Cell<usize>,
+}
+
+impl ScriptedPlayer {
+    /// Creates a player who will take the given actions, in order, whenever it's their turn to act.
+    pub fn with_actions(buy_in: u32, actions: &[actors::Action]) -> ScriptedPlayer {
+        ScriptedPlayer {
+            actions: actions.to_vec(),
+            ..ScriptedPlayer::new(buy_in)
+        }
+    }
+}
+
+impl actors::Actor for ScriptedPlayer {
+    fn hand_mut(&mut self) -> &mut cards::Hand {
+        let active = self.active_hand_index();
+        &mut self.hands[active].cards
+    }
+
+    fn hand(&self) -> &[cards::Card] {
+        self.hands[self.active_hand_index()].cards.as_slice()
+    }
+}
+
+impl players::Player for ScriptedPlayer {
+    fn new(buy_in: u32) -> ScriptedPlayer {
+        let money = if buy_in > 0 { Some(buy_in) } else { None };
+
+        ScriptedPlayer {
+            hands: vec![players::PlayerHand::default()],
+            money,
+            actions: Vec::new(),
+            next_action: Cell::new(0),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Scripted"
+    }
+
+    fn money_mut(&mut self) -> &mut Option<u32> {
+        &mut self.money
+    }
+
+    fn money(&self) -> Option<u32> {
+        self.money
+    }
+
+    fn hands_mut(&mut self) -> &mut Vec<players::PlayerHand> {
+        &mut self.hands
+    }
+
+    fn hands(&self) -> &[players::PlayerHand] {
+        self.hands.as_slice()
+    }
+
+    fn place_bet(&mut self, _rules: &blackjack::TableRules) {}
+
+    fn decide_action(
+        &self,
+        _dealer_upcard: &cards::Card,
+        _allowed_actions: &[actors::Action],
+    ) -> actors::Action {
+        let next = self.next_action.get();
+        self.next_action.set(next + 1);
+        self.actions
+            .get(next)
+            .copied()
+            .unwrap_or(actors::Action::Stand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::actors::tests as actor_tests;
+
+    #[test]
+    fn scripted_player_adds_card_to_hand() {
+        actor_tests::adds_card_to_hand(ScriptedPlayer::new(0));
+    }
+
+    #[test]
+    fn scripted_player_follows_script_then_stands() {
+        let player =
+            ScriptedPlayer::with_actions(0, &[actors::Action::Hit, actors::Action::DoubleDown]);
+        let upcard = actor_tests::create_card_from_value(10);
+
+        assert_eq!(actors::Action::Hit, player.decide_action(&upcard, &[]));
+        assert_eq!(
+            actors::Action::DoubleDown,
+            player.decide_action(&upcard, &[])
+        );
+        assert_eq!(actors::Action::Stand, player.decide_action(&upcard, &[]));
+    }
+}
@@ -0,0 +1,556 @@
+//! Card counting: keeping track of the cards that have been played since the last shuffle, to
+//! know when what's left in the shoe favors the player. Low cards left in the shoe help the
+//! dealer and high cards help the player, so a count goes up as low cards come out and down
+//! as high cards do.
+//!
+//! A [`CountingSystem`] says how much each card is worth to the count, and a [`CardCount`]
+//! keeps the count for a shoe with any system. Counts can follow a game by
+//! [observing](CardCount::observe) the same events players are shown.
+//!
+//! ```
+//! use praeses_blackjack::blackjack::counting::{CardCount, HiLo, WongHalves};
+//! use praeses_blackjack::cards::{Card, Rank, Suit};
+//!
+//! let mut hi_lo = CardCount::new(HiLo, 2);
+//! let mut halves = CardCount::new(WongHalves, 2);
+//! for rank in [Rank::Five, Rank::Two, Rank::Six, Rank::Three] {
+//!     hi_lo.count_card(&Card { rank, suit: Suit::Club });
+//!     halves.count_card(&Card { rank, suit: Suit::Club });
+//! }
+//! assert_eq!(4.0, hi_lo.running_count());
+//! assert_eq!(4.0, halves.running_count());
+//! ```
+
+use std::str::FromStr;
+
+use crate::blackjack::strategy::DeviationTable;
+use crate::blackjack::Event;
+use crate::cards::{self, Rank};
+
+/// A way of counting cards: how much each card adds to (or takes away from) the count.
+pub trait CountingSystem {
+    /// The name the system usually goes by, like "Hi-Lo".
+    fn name(&self) -> &str;
+
+    /// Gets what a card of the given rank adds to the count.
+    fn tag(&self, rank: Rank) -> f64;
+
+    /// Returns true if a whole deck counts to zero. The running count of a balanced system
+    /// gets divided by the decks left to get the true count, while unbalanced systems are
+    /// played straight off the running count.
+    fn is_balanced(&self) -> bool;
+
+    /// What the count starts at for a freshly shuffled shoe with the given number of decks.
+    /// Balanced systems start at zero, and unbalanced systems can start lower so that the
+    /// running count doesn't need converting.
+    fn initial_running_count(&self, _num_decks: u32) -> f64 {
+        0.0
+    }
+
+    /// The index plays worked out for the system's true count, if any have been. Only Hi-Lo
+    /// comes with them, so the other systems play basic strategy unless given their own.
+    fn index_plays(&self) -> DeviationTable {
+        DeviationTable::new()
+    }
+}
+
+/// The Hi-Lo count, the most popular system: +1 for 2 through 6, and -1 for tens and aces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct HiLo;
+
+impl CountingSystem for HiLo {
+    fn name(&self) -> &str {
+        "Hi-Lo"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Two | Rank::Three | Rank::Four | Rank::Five | Rank::Six => 1.0,
+            Rank::Seven | Rank::Eight | Rank::Nine => 0.0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King | Rank::Ace => -1.0,
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        true
+    }
+
+    /// The Fab 4 surrenders and the Illustrious 18.
+    fn index_plays(&self) -> DeviationTable {
+        DeviationTable::fab_4().and(DeviationTable::illustrious_18())
+    }
+}
+
+/// The Knock-Out (KO) count: Hi-Lo with sevens counted as low cards too. It's unbalanced,
+/// starting at 4 minus 4 for each deck, so that the running count can be used as it is.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Ko;
+
+impl CountingSystem for Ko {
+    fn name(&self) -> &str {
+        "KO"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Seven => 1.0,
+            rank => HiLo.tag(rank),
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        false
+    }
+
+    fn initial_running_count(&self, num_decks: u32) -> f64 {
+        4.0 - 4.0 * f64::from(num_decks)
+    }
+}
+
+/// The Hi-Opt I count: +1 for 3 through 6, and -1 for tens. Twos and aces aren't counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct HiOptI;
+
+impl CountingSystem for HiOptI {
+    fn name(&self) -> &str {
+        "Hi-Opt I"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Three | Rank::Four | Rank::Five | Rank::Six => 1.0,
+            Rank::Ace | Rank::Two | Rank::Seven | Rank::Eight | Rank::Nine => 0.0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => -1.0,
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        true
+    }
+}
+
+/// The Hi-Opt II count: +1 for 2, 3, 6 and 7, +2 for 4 and 5, and -2 for tens. Aces aren't
+/// counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct HiOptII;
+
+impl CountingSystem for HiOptII {
+    fn name(&self) -> &str {
+        "Hi-Opt II"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Two | Rank::Three | Rank::Six | Rank::Seven => 1.0,
+            Rank::Four | Rank::Five => 2.0,
+            Rank::Ace | Rank::Eight | Rank::Nine => 0.0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => -2.0,
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        true
+    }
+}
+
+/// The Omega II count: +1 for 2, 3 and 7, +2 for 4 through 6, -1 for 9, and -2 for tens.
+/// Aces aren't counted.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct OmegaII;
+
+impl CountingSystem for OmegaII {
+    fn name(&self) -> &str {
+        "Omega II"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Two | Rank::Three | Rank::Seven => 1.0,
+            Rank::Four | Rank::Five | Rank::Six => 2.0,
+            Rank::Ace | Rank::Eight => 0.0,
+            Rank::Nine => -1.0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => -2.0,
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        true
+    }
+}
+
+/// The Zen count: +1 for 2, 3 and 7, +2 for 4 through 6, -1 for aces, and -2 for tens.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct Zen;
+
+impl CountingSystem for Zen {
+    fn name(&self) -> &str {
+        "Zen"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Two | Rank::Three | Rank::Seven => 1.0,
+            Rank::Four | Rank::Five | Rank::Six => 2.0,
+            Rank::Eight | Rank::Nine => 0.0,
+            Rank::Ace => -1.0,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King => -2.0,
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        true
+    }
+}
+
+/// Stanford Wong's Halves count, which counts in half points to follow each card's effect
+/// more closely: +1.5 for 5, +1 for 3, 4 and 6, +0.5 for 2 and 7, -0.5 for 9, and -1 for tens
+/// and aces.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct WongHalves;
+
+impl CountingSystem for WongHalves {
+    fn name(&self) -> &str {
+        "Wong Halves"
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        match rank {
+            Rank::Five => 1.5,
+            Rank::Three | Rank::Four | Rank::Six => 1.0,
+            Rank::Two | Rank::Seven => 0.5,
+            Rank::Eight => 0.0,
+            Rank::Nine => -0.5,
+            Rank::Ten | Rank::Jack | Rank::Queen | Rank::King | Rank::Ace => -1.0,
+        }
+    }
+
+    fn is_balanced(&self) -> bool {
+        true
+    }
+}
+
+/// Any of the counting systems above, picked by name (like on the commandline). It counts
+/// exactly the same as the system it names.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum CountStyle {
+    #[default]
+    HiLo,
+    Ko,
+    HiOptI,
+    HiOptII,
+    OmegaII,
+    Zen,
+    WongHalves,
+}
+
+impl CountStyle {
+    /// Gets the counting system this style names.
+    fn system(&self) -> &dyn CountingSystem {
+        match self {
+            Self::HiLo => &HiLo,
+            Self::Ko => &Ko,
+            Self::HiOptI => &HiOptI,
+            Self::HiOptII => &HiOptII,
+            Self::OmegaII => &OmegaII,
+            Self::Zen => &Zen,
+            Self::WongHalves => &WongHalves,
+        }
+    }
+}
+
+impl CountingSystem for CountStyle {
+    fn name(&self) -> &str {
+        self.system().name()
+    }
+
+    fn tag(&self, rank: Rank) -> f64 {
+        self.system().tag(rank)
+    }
+
+    fn is_balanced(&self) -> bool {
+        self.system().is_balanced()
+    }
+
+    fn initial_running_count(&self, num_decks: u32) -> f64 {
+        self.system().initial_running_count(num_decks)
+    }
+
+    fn index_plays(&self) -> DeviationTable {
+        self.system().index_plays()
+    }
+}
+
+impl FromStr for CountStyle {
+    type Err = &'static str;
+
+    /// Parses a counting system from the names used on the commandline.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "hi-lo" | "hilo" => Ok(Self::HiLo),
+            "ko" | "knock-out" => Ok(Self::Ko),
+            "hi-opt-1" | "hi-opt-i" => Ok(Self::HiOptI),
+            "hi-opt-2" | "hi-opt-ii" => Ok(Self::HiOptII),
+            "omega-2" | "omega-ii" => Ok(Self::OmegaII),
+            "zen" => Ok(Self::Zen),
+            "wong-halves" | "halves" => Ok(Self::WongHalves),
+            _ => Err(
+                "Invalid counting system (try hi-lo, ko, hi-opt-1, hi-opt-2, omega-2, zen, or wong-halves)",
+            ),
+        }
+    }
+}
+
+/// The count for a shoe, kept with some counting system: the running count of every card
+/// seen since the shoe was shuffled, along with how many cards are left in the shoe to work
+/// out the true count.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CardCount<S: CountingSystem> {
+    system: S,
+    num_decks: u32,
+    deck_size: usize,
+    running_count: f64,
+    cards_remaining: usize,
+}
+
+impl<S: CountingSystem> CardCount<S> {
+    /// Starts counting a freshly shuffled shoe with the given number of standard decks.
+    pub fn new(system: S, num_decks: u32) -> CardCount<S> {
+        CardCount {
+            running_count: system.initial_running_count(num_decks),
+            cards_remaining: num_decks as usize * cards::STANDARD_DECK_COUNT,
+            system,
+            num_decks,
+            deck_size: cards::STANDARD_DECK_COUNT,
+        }
+    }
+
+    /// Counts a shoe made of decks with `deck_size` cards each instead of standard decks,
+    /// like the 48 card decks in Spanish 21.
+    ///
+    /// ```
+    /// use praeses_blackjack::blackjack::counting::{CardCount, HiLo};
+    ///
+    /// let count = CardCount::new(HiLo, 6).with_deck_size(48);
+    /// assert_eq!(6 * 48, count.cards_remaining());
+    /// assert_eq!(6.0, count.decks_remaining());
+    /// ```
+    pub fn with_deck_size(mut self, deck_size: usize) -> CardCount<S> {
+        self.deck_size = deck_size.max(1);
+        self.cards_remaining = self.num_decks as usize * self.deck_size;
+        self
+    }
+
+    /// Gets the counting system the count is kept with.
+    pub fn system(&self) -> &S {
+        &self.system
+    }
+
+    /// Counts a card that was seen.
+    pub fn count_card(&mut self, card: &cards::Card) {
+        self.running_count += self.system.tag(card.rank);
+    }
+
+    /// The count of every card seen since the shoe was shuffled (starting from the system's
+    /// initial count).
+    pub fn running_count(&self) -> f64 {
+        self.running_count
+    }
+
+    /// How many cards are left in the shoe.
+    pub fn cards_remaining(&self) -> usize {
+        self.cards_remaining
+    }
+
+    /// How many decks are left in the shoe. Never less than half a deck, so the last few
+    /// cards in the shoe don't blow up the true count.
+    pub fn decks_remaining(&self) -> f64 {
+        (self.cards_remaining as f64 / self.deck_size as f64).max(0.5)
+    }
+
+    /// The running count for each deck left in the shoe. A running count of +6 means a lot
+    /// more with one deck left than with six, and the true count is what betting and
+    /// strategy decisions go by. Unbalanced systems are played straight off the running
+    /// count, so for them this is just the running count.
+    pub fn true_count(&self) -> f64 {
+        if self.system.is_balanced() {
+            self.running_count / self.decks_remaining()
+        } else {
+            self.running_count
+        }
+    }
+
+    /// Starts the count over for a shoe that was just shuffled with `cards_remaining` cards in it.
+    pub fn reset(&mut self, cards_remaining: usize) {
+        self.running_count = self.system.initial_running_count(self.num_decks);
+        self.cards_remaining = cards_remaining;
+    }
+
+    /// Keeps the count up to date with something that happened in the game: counting every
+    /// card turned face up (including the dealer's hole card once it's revealed) and starting
+    /// over when the shoe is reshuffled.
+    pub fn observe(&mut self, event: &Event) {
+        match event {
+            Event::ShoeReshuffled => self.reset(self.cards_remaining),
+            Event::RoundStarted {
+                cards_remaining, ..
+            } => {
+                // Cards only go back into the shoe when it's shuffled (like a continuous
+                // shuffling machine does every round), so whatever was counted is gone.
+                if *cards_remaining > self.cards_remaining {
+                    self.reset(*cards_remaining);
+                }
+                self.cards_remaining = *cards_remaining;
+            }
+            Event::CardDealt { card, .. } => {
+                self.cards_remaining = self.cards_remaining.saturating_sub(1);
+                if let Some(card) = card {
+                    self.count_card(card);
+                }
+            }
+            Event::HoleCardRevealed { card } => self.count_card(card),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::Participant;
+    use crate::cards::{Card, Suit};
+
+    const STYLES: [CountStyle; 7] = [
+        CountStyle::HiLo,
+        CountStyle::Ko,
+        CountStyle::HiOptI,
+        CountStyle::HiOptII,
+        CountStyle::OmegaII,
+        CountStyle::Zen,
+        CountStyle::WongHalves,
+    ];
+
+    fn card(rank: Rank) -> Card {
+        Card {
+            rank,
+            suit: Suit::Heart,
+        }
+    }
+
+    #[test]
+    fn balanced_systems_count_a_whole_deck_to_zero() {
+        for style in STYLES {
+            let mut count = CardCount::new(style, 1);
+            for card in cards::standard_deck() {
+                count.count_card(&card);
+            }
+            assert_eq!(
+                style.is_balanced(),
+                count.running_count() == 0.0,
+                "{} ended a deck at {}",
+                style.name(),
+                count.running_count()
+            );
+        }
+    }
+
+    #[test]
+    fn unbalanced_counts_start_below_zero() {
+        let mut count = CardCount::new(Ko, 6);
+        assert_eq!(-20.0, count.running_count());
+
+        // A full six decks counts up by 4 a deck, ending at the same 4 no matter how many decks.
+        for card in cards::create_multideck(6) {
+            count.count_card(&card);
+        }
+        assert_eq!(4.0, count.running_count());
+        assert_eq!(4.0, count.true_count());
+
+        count.observe(&Event::ShoeReshuffled);
+        assert_eq!(-20.0, count.running_count());
+    }
+
+    #[test]
+    fn systems_tag_cards_differently() {
+        let tags = |rank| STYLES.map(|style| style.tag(rank));
+        assert_eq!([1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 1.5], tags(Rank::Five));
+        assert_eq!([0.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.5], tags(Rank::Seven));
+        assert_eq!([-1.0, -1.0, 0.0, 0.0, 0.0, -1.0, -1.0], tags(Rank::Ace));
+        assert_eq!([-1.0, -1.0, -1.0, -2.0, -2.0, -2.0, -1.0], tags(Rank::King));
+    }
+
+    #[test]
+    fn true_count_divides_by_decks_remaining() {
+        let mut count = CardCount::new(HiLo, 6);
+        count.reset(3 * cards::STANDARD_DECK_COUNT);
+        for _ in 0..6 {
+            count.count_card(&card(Rank::Four));
+        }
+        assert_eq!(6.0, count.running_count());
+        assert_eq!(3.0, count.decks_remaining());
+        assert_eq!(2.0, count.true_count());
+
+        // The last few cards count as half a deck.
+        count.cards_remaining = 5;
+        assert_eq!(12.0, count.true_count());
+    }
+
+    #[test]
+    fn follows_the_cards_dealt_in_a_game() {
+        let mut count = CardCount::new(HiLo, 1);
+        let events = [
+            Event::RoundStarted {
+                cards_seen: 1,
+                cards_remaining: 51,
+            },
+            Event::CardDealt {
+                to: Participant::Player("Bot".to_string()),
+                card: Some(card(Rank::Five)),
+            },
+            Event::CardDealt {
+                to: Participant::Dealer,
+                card: None,
+            },
+            Event::CardDealt {
+                to: Participant::Player("Bot".to_string()),
+                card: Some(card(Rank::Six)),
+            },
+            Event::CardDealt {
+                to: Participant::Dealer,
+                card: Some(card(Rank::Eight)),
+            },
+            Event::HoleCardRevealed {
+                card: card(Rank::Three),
+            },
+        ];
+        for event in &events {
+            count.observe(event);
+        }
+        assert_eq!(3.0, count.running_count());
+        assert_eq!(47, count.cards_remaining());
+
+        count.observe(&Event::ShoeReshuffled);
+        assert_eq!(0.0, count.running_count());
+    }
+
+    #[test]
+    fn cards_going_back_in_the_shoe_start_the_count_over() {
+        let mut count = CardCount::new(HiLo, 1);
+        count.observe(&Event::CardDealt {
+            to: Participant::Dealer,
+            card: Some(card(Rank::Two)),
+        });
+        count.observe(&Event::RoundStarted {
+            cards_seen: 0,
+            cards_remaining: cards::STANDARD_DECK_COUNT,
+        });
+        assert_eq!(0.0, count.running_count());
+    }
+
+    #[test]
+    fn parses_count_styles() {
+        assert_eq!(Ok(CountStyle::OmegaII), "Omega-II".parse());
+        assert_eq!(Ok(CountStyle::WongHalves), "halves".parse());
+        assert_eq!("Hi-Opt I", "hi-opt-1".parse::<CountStyle>().unwrap().name());
+        assert!("red-seven".parse::<CountStyle>().is_err());
+    }
+}
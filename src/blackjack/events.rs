@@ -0,0 +1,244 @@
+//! Everything that happens in a game of blackjack, as data. The game engine doesn't print
+//! anything or read any input itself--it tells a [`Frontend`] what happened through these
+//! events, and players make their decisions through the [`Player`](crate::Player) trait.
+//! The command line game in [`cli`](crate::cli) is one front end built on top of this.
+//!
+//! Anything else that wants to follow the game (logging, statistics, another UI) can be an
+//! [`Observer`] and subscribe to an [`EventStream`] alongside the front end.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+use std::sync::mpsc;
+
+use crate::blackjack::{actors, PlayerRoundResult};
+use crate::cards;
+
+/// Who an event happened to: one of the players (by name) or the dealer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Participant {
+    Player(String),
+    Dealer,
+}
+
+/// Something that happened during a game of blackjack.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    /// A new round is starting, before any bets are placed. Has how many cards have been
+    /// dealt from the shoe since it was last shuffled, and how many are left in it.
+    RoundStarted {
+        cards_seen: usize,
+        cards_remaining: usize,
+    },
+    /// The cut card came out, so every card was gathered back into the shoe and shuffled before the round.
+    ShoeReshuffled,
+    /// Cards were burned off the top of the freshly shuffled shoe, without anybody seeing them.
+    CardsBurned { count: usize },
+    /// A player went broke and was given the buy-in again to keep playing.
+    PlayerBoughtIn { player: String, amount: u32 },
+    /// A player bet on their hand for the round.
+    BetPlaced { player: String, amount: u32 },
+    /// A card was dealt. The dealer's hole card is dealt face down, so it's `None` until it's revealed.
+    CardDealt {
+        to: Participant,
+        card: Option<cards::Card>,
+    },
+    /// The dealer turned over their hole card.
+    HoleCardRevealed { card: cards::Card },
+    /// The dealer shows an ace, so players are being offered insurance (or even money).
+    InsuranceOffered { dealer_upcard: cards::Card },
+    /// A player put money up as insurance against the dealer having blackjack.
+    InsurancePlaced { player: String, amount: u32 },
+    /// A player with a natural took even money instead of risking a stand-off.
+    EvenMoneyTaken { player: String },
+    /// The dealer checked for blackjack and paid out (or took) a player's insurance bet.
+    /// Insurance pays 2:1, so a player whose insurance won gets back three times the amount.
+    InsuranceSettled {
+        player: String,
+        amount: u32,
+        dealer_has_natural: bool,
+        money: Option<u32>,
+    },
+    /// The dealer might have blackjack, so players are being offered early surrender.
+    EarlySurrenderOffered { dealer_upcard: cards::Card },
+    /// Somebody has blackjack.
+    Blackjack { who: Participant },
+    /// A player's turn is starting.
+    TurnStarted { player: String },
+    /// A player is about to act on one of their hands. The hand number counts from 1, and
+    /// is only given when the player has more than one hand.
+    HandInPlay {
+        player: String,
+        hand_number: Option<usize>,
+        cards: cards::Hand,
+    },
+    /// Somebody took an action on their hand.
+    ActionTaken {
+        who: Participant,
+        action: actors::Action,
+    },
+    /// A player tried an action that isn't allowed on their hand, so they have to pick again.
+    ActionRejected {
+        player: String,
+        reason: &'static str,
+    },
+    /// Somebody's hand went over 21.
+    HandBusted { who: Participant },
+    /// The players are done, so the dealer is taking their turn.
+    DealerTurnStarted,
+    /// One of a player's hands was settled at the end of the round. Winnings is how much
+    /// money they got back (including their bet), and money is what they have afterwards.
+    HandSettled {
+        player: String,
+        hand_number: Option<usize>,
+        result: PlayerRoundResult,
+        bet: Option<u32>,
+        winnings: u32,
+        money: Option<u32>,
+    },
+    /// Every hand has been settled and the cards are cleared off the table.
+    /// Has the results for each player's hands, in the order they're seated.
+    RoundSettled {
+        results: Vec<(String, Vec<PlayerRoundResult>)>,
+    },
+}
+
+/// Something that runs a game of blackjack for people: it gets told everything that happens
+/// and decides when the game is over. Player decisions go through each [`Player`](crate::Player).
+pub trait Frontend {
+    /// Handles something that happened in the game, like showing it to the people playing.
+    fn handle_event(&mut self, event: &Event);
+
+    /// Once a round is settled, decides whether to deal another one.
+    fn play_another_round(&mut self) -> bool;
+}
+
+/// Something that wants to know what happens in a game, without having any say in it.
+pub trait Observer {
+    /// Gets told about something that happened in the game.
+    fn notify(&mut self, event: &Event);
+}
+
+/// Sends every event down a channel, so they can be handled somewhere else (like another thread).
+/// Once the receiving end is dropped, events are quietly thrown away.
+impl Observer for mpsc::Sender<Event> {
+    fn notify(&mut self, event: &Event) {
+        let _ = self.send(event.clone());
+    }
+}
+
+/// Lets an observer be subscribed while still being looked at from outside the game,
+/// like statistics that are read once the game is over.
+impl<O: Observer> Observer for Rc<RefCell<O>> {
+    fn notify(&mut self, event: &Event) {
+        self.borrow_mut().notify(event);
+    }
+}
+
+/// A front end along with every observer subscribed to the game. Events go to the front end
+/// first and then to each observer in the order they subscribed. It is a front end itself,
+/// so it can be played with in place of the one it wraps.
+pub struct EventStream<'a> {
+    frontend: &'a mut dyn Frontend,
+    observers: Vec<Box<dyn Observer + 'a>>,
+}
+
+impl<'a> EventStream<'a> {
+    /// Creates a stream for the given front end without any observers yet.
+    pub fn new(frontend: &'a mut dyn Frontend) -> EventStream<'a> {
+        EventStream {
+            frontend,
+            observers: Vec::new(),
+        }
+    }
+
+    /// Subscribes an observer to every event from here on.
+    pub fn subscribe(&mut self, observer: impl Observer + 'a) {
+        self.observers.push(Box::new(observer));
+    }
+}
+
+impl Frontend for EventStream<'_> {
+    fn handle_event(&mut self, event: &Event) {
+        self.frontend.handle_event(event);
+        for observer in &mut self.observers {
+            observer.notify(event);
+        }
+    }
+
+    fn play_another_round(&mut self) -> bool {
+        self.frontend.play_another_round()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A front end that counts the events it sees and never plays another round.
+    #[derive(Default)]
+    struct CountingFrontend {
+        events: usize,
+    }
+
+    impl Frontend for CountingFrontend {
+        fn handle_event(&mut self, _event: &Event) {
+            self.events += 1;
+        }
+
+        fn play_another_round(&mut self) -> bool {
+            false
+        }
+    }
+
+    /// An observer that keeps every event it sees.
+    #[derive(Default)]
+    struct Recorder {
+        events: Vec<Event>,
+    }
+
+    impl Observer for Recorder {
+        fn notify(&mut self, event: &Event) {
+            self.events.push(event.clone());
+        }
+    }
+
+    #[test]
+    fn stream_sends_events_to_every_subscriber() {
+        let mut frontend = CountingFrontend::default();
+        let recorder = Rc::new(RefCell::new(Recorder::default()));
+        let (sender, receiver) = mpsc::channel();
+
+        let mut stream = EventStream::new(&mut frontend);
+        stream.subscribe(Rc::clone(&recorder));
+        stream.subscribe(sender);
+
+        stream.handle_event(&Event::DealerTurnStarted);
+        stream.handle_event(&Event::ShoeReshuffled);
+        assert!(!stream.play_another_round());
+        drop(stream);
+
+        assert_eq!(2, frontend.events);
+        assert_eq!(
+            vec![Event::DealerTurnStarted, Event::ShoeReshuffled],
+            recorder.borrow().events
+        );
+        assert_eq!(
+            vec![Event::DealerTurnStarted, Event::ShoeReshuffled],
+            receiver.try_iter().collect::<Vec<Event>>()
+        );
+    }
+
+    #[test]
+    fn dropped_channel_does_not_stop_the_game() {
+        let mut frontend = CountingFrontend::default();
+        let (sender, receiver) = mpsc::channel();
+        drop(receiver);
+
+        let mut stream = EventStream::new(&mut frontend);
+        stream.subscribe(sender);
+        stream.handle_event(&Event::DealerTurnStarted);
+        drop(stream);
+
+        assert_eq!(1, frontend.events);
+    }
+}
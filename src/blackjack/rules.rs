@@ -0,0 +1,429 @@
+//! Table rules: everything about a game of blackjack that changes from casino to casino.
+//! Rules can be built in code, picked from a preset of common rule sets, or (with the `serde`
+//! feature) loaded from a TOML or JSON rules file.
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use std::{fs, io, path::Path};
+
+use crate::cards::Penetration;
+
+/// The rules a blackjack table plays by.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default, deny_unknown_fields))]
+pub struct TableRules {
+    /// How many decks are used to create the deck (most popular is six for a 312 card game).
+    pub num_decks: u32,
+    /// How deep into the shoe the dealer deals before the cut card comes out and the shoe is
+    /// reshuffled (once the round is over).
+    pub penetration: Penetration,
+    /// How many cards either side of the penetration the cut card can land, picked at random
+    /// every shuffle like a real cut. With 0 it always goes exactly at the penetration.
+    pub cut_card_spread: u32,
+    /// How many cards are burned off the top of the shoe after every shuffle.
+    pub burn_cards: u32,
+    /// Payout for winning in blackjack, usually 3:2 or 6:5.
+    /// Higher is better for the players, lower is better for the house.
+    pub payout_ratio: f64,
+    /// Whether the dealer hits on a soft 17 (H17) instead of standing (S17).
+    /// Hitting soft 17 is better for the house.
+    pub dealer_hits_soft_17: bool,
+    /// Whether the dealer takes a hole card and checks it for blackjack before the players' turns.
+    pub hole_card_policy: HoleCardPolicy,
+    /// Which first two cards a player is allowed to double down on.
+    pub double_down_rule: DoubleDownRule,
+    /// Whether players can double down on hands they made by splitting a pair.
+    pub double_after_split: bool,
+    /// The most hands a player can have in a round from splitting and resplitting pairs.
+    /// Usually 4, and setting it to 1 disables splitting altogether.
+    pub max_split_hands: u32,
+    /// Whether aces that were split can be split again if another ace is dealt to them.
+    pub resplit_aces: bool,
+    /// Whether hands made from splitting aces can keep taking cards. Most casinos only give
+    /// split aces one card each.
+    pub hit_split_aces: bool,
+    /// When (if ever) players are allowed to surrender half their bet instead of playing their hand.
+    pub surrender_rule: SurrenderRule,
+    /// Whether players are offered insurance (and even money) when the dealer shows an ace.
+    pub offer_insurance: bool,
+    /// The smallest bet a player can make on a round (betting nothing sits the round out).
+    pub min_bet: u32,
+    /// The largest bet a player can make on a round, if there is a limit.
+    pub max_bet: Option<u32>,
+}
+
+impl Default for TableRules {
+    /// Six decks dealt 80% deep with one burn card, 3:2 blackjack, dealer stands on soft 17, double on anything
+    /// (including after splits), split up to four hands, and no surrender.
+    fn default() -> Self {
+        TableRules {
+            num_decks: 6,
+            penetration: Penetration::default(),
+            cut_card_spread: 0,
+            burn_cards: 1,
+            payout_ratio: 3.0 / 2.0,
+            dealer_hits_soft_17: false,
+            hole_card_policy: HoleCardPolicy::Peek,
+            double_down_rule: DoubleDownRule::AnyTwoCards,
+            double_after_split: true,
+            max_split_hands: 4,
+            resplit_aces: false,
+            hit_split_aces: false,
+            surrender_rule: SurrenderRule::NoSurrender,
+            offer_insurance: true,
+            min_bet: 1,
+            max_bet: None,
+        }
+    }
+}
+
+impl TableRules {
+    /// Classic Las Vegas Strip rules: six decks, dealer stands on soft 17, doubling after
+    /// splits, and late surrender.
+    pub fn vegas_strip() -> TableRules {
+        TableRules {
+            num_decks: 6,
+            surrender_rule: SurrenderRule::Late,
+            min_bet: 25,
+            max_bet: Some(5000),
+            ..TableRules::default()
+        }
+    }
+
+    /// Atlantic City rules: eight decks, dealer stands on soft 17, doubling after splits,
+    /// and late surrender.
+    pub fn atlantic_city() -> TableRules {
+        TableRules {
+            num_decks: 8,
+            surrender_rule: SurrenderRule::Late,
+            min_bet: 15,
+            max_bet: Some(2000),
+            ..TableRules::default()
+        }
+    }
+
+    /// Downtown Las Vegas rules: a double deck game where the dealer hits soft 17.
+    pub fn downtown() -> TableRules {
+        TableRules {
+            num_decks: 2,
+            dealer_hits_soft_17: true,
+            min_bet: 5,
+            max_bet: Some(500),
+            ..TableRules::default()
+        }
+    }
+
+    /// European rules: the dealer doesn't take a hole card until the players are done, doubling
+    /// is only on 9, 10, or 11, and pairs can only be split once.
+    pub fn european() -> TableRules {
+        TableRules {
+            num_decks: 6,
+            hole_card_policy: HoleCardPolicy::NoHoleCard,
+            double_down_rule: DoubleDownRule::NineToEleven,
+            double_after_split: false,
+            max_split_hands: 2,
+            min_bet: 10,
+            max_bet: Some(1000),
+            ..TableRules::default()
+        }
+    }
+
+    /// Loads table rules from a rules file. Files ending in `.json` are read as JSON and anything
+    /// else as TOML. Any rules missing from the file keep their default values.
+    #[cfg(feature = "serde")]
+    pub fn from_file(path: &Path) -> Result<TableRules, RulesFileError> {
+        let contents = fs::read_to_string(path).map_err(RulesFileError::Io)?;
+        let is_json = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+
+        if is_json {
+            serde_json::from_str(&contents).map_err(RulesFileError::Json)
+        } else {
+            toml::from_str(&contents).map_err(RulesFileError::Toml)
+        }
+    }
+
+    /// Checks that a bet follows the table's limits. Betting nothing is always allowed, it
+    /// just means sitting the round out.
+    pub fn check_bet(&self, bet: u32) -> Result<(), String> {
+        if bet == 0 {
+            return Ok(());
+        }
+        if bet < self.min_bet {
+            return Err(format!(
+                "The minimum bet at this table is ${}.",
+                self.min_bet
+            ));
+        }
+        match self.max_bet {
+            Some(max_bet) if bet > max_bet => {
+                Err(format!("The maximum bet at this table is ${}.", max_bet))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Errors that can happen when loading table rules from a file.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub enum RulesFileError {
+    /// The rules file couldn't be read.
+    Io(io::Error),
+    /// The rules file wasn't valid TOML or had invalid rules.
+    Toml(toml::de::Error),
+    /// The rules file wasn't valid JSON or had invalid rules.
+    Json(serde_json::Error),
+}
+
+#[cfg(feature = "serde")]
+impl fmt::Display for RulesFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RulesFileError::Io(e) => write!(f, "Couldn't read rules file: {}", e),
+            RulesFileError::Toml(e) => write!(f, "Invalid TOML rules file: {}", e),
+            RulesFileError::Json(e) => write!(f, "Invalid JSON rules file: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl std::error::Error for RulesFileError {}
+
+/// Common sets of table rules that can be picked by name.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum RulesPreset {
+    VegasStrip,
+    AtlanticCity,
+    Downtown,
+    European,
+}
+
+impl RulesPreset {
+    /// Gets the table rules for this preset.
+    pub fn rules(&self) -> TableRules {
+        match self {
+            Self::VegasStrip => TableRules::vegas_strip(),
+            Self::AtlanticCity => TableRules::atlantic_city(),
+            Self::Downtown => TableRules::downtown(),
+            Self::European => TableRules::european(),
+        }
+    }
+}
+
+impl FromStr for RulesPreset {
+    type Err = &'static str;
+
+    /// Parses a preset from the names used on the commandline.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "vegas-strip" | "vegas" | "strip" => Ok(Self::VegasStrip),
+            "atlantic-city" | "ac" => Ok(Self::AtlanticCity),
+            "downtown" => Ok(Self::Downtown),
+            "european" | "europe" => Ok(Self::European),
+            _ => Err("Invalid preset (try vegas-strip, atlantic-city, downtown, or european)"),
+        }
+    }
+}
+
+impl fmt::Display for RulesPreset {
+    /// Shows a preset by the name used to pick it on the commandline.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VegasStrip => write!(f, "vegas-strip"),
+            Self::AtlanticCity => write!(f, "atlantic-city"),
+            Self::Downtown => write!(f, "downtown"),
+            Self::European => write!(f, "european"),
+        }
+    }
+}
+
+/// Rules for when the dealer gets their second card.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum HoleCardPolicy {
+    /// The dealer gets a face-down hole card along with the players' cards, and peeks at it for
+    /// blackjack before anyone plays, as in American casinos.
+    Peek,
+    /// The dealer only gets their second card after the players are done (European no hole card).
+    /// If it gives them blackjack, players lose everything they bet, including doubles and splits.
+    NoHoleCard,
+}
+
+/// Rules for which hands a player is allowed to double down on.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum DoubleDownRule {
+    /// Doubling down isn't allowed at all.
+    Never,
+    /// Players can double down on any first two cards.
+    AnyTwoCards,
+    /// Players can only double down when their first two cards are worth 9, 10, or 11.
+    NineToEleven,
+    /// Players can only double down when their first two cards are worth 10 or 11.
+    TenToEleven,
+}
+
+impl DoubleDownRule {
+    /// Returns true if this rule lets a player double down on a two card hand with the given value.
+    pub fn allows(&self, hand_value: u32) -> bool {
+        match self {
+            Self::Never => false,
+            Self::AnyTwoCards => true,
+            Self::NineToEleven => (9..=11).contains(&hand_value),
+            Self::TenToEleven => (10..=11).contains(&hand_value),
+        }
+    }
+}
+
+impl FromStr for DoubleDownRule {
+    type Err = &'static str;
+
+    /// Parses a double down rule from its name in rules files, like "nine-to-eleven". The
+    /// short names "any", "9-11" and "10-11" work too.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "never" | "none" => Ok(Self::Never),
+            "any-two-cards" | "any" => Ok(Self::AnyTwoCards),
+            "nine-to-eleven" | "9-11" => Ok(Self::NineToEleven),
+            "ten-to-eleven" | "10-11" => Ok(Self::TenToEleven),
+            _ => Err(
+                "Invalid double down rule (try any-two-cards, nine-to-eleven, ten-to-eleven, or never)",
+            ),
+        }
+    }
+}
+
+/// Rules for when players can surrender, giving up half their bet to end their hand.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+pub enum SurrenderRule {
+    /// Surrendering isn't allowed.
+    NoSurrender,
+    /// Players can surrender their first two cards, but only after the dealer checks for blackjack.
+    Late,
+    /// Players can surrender before the dealer checks for blackjack, as in some European games.
+    /// They can still surrender on their turn afterwards too.
+    Early,
+}
+
+impl FromStr for SurrenderRule {
+    type Err = &'static str;
+
+    /// Parses a surrender rule from its name in rules files, like "no-surrender" (or just "none").
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "no-surrender" | "none" | "no" => Ok(Self::NoSurrender),
+            "late" => Ok(Self::Late),
+            "early" => Ok(Self::Early),
+            _ => Err("Invalid surrender rule (try no-surrender, late, or early)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn double_down_rules_check_hand_value() {
+        assert!(DoubleDownRule::AnyTwoCards.allows(17));
+        assert!(!DoubleDownRule::Never.allows(11));
+        assert!(DoubleDownRule::NineToEleven.allows(9));
+        assert!(!DoubleDownRule::NineToEleven.allows(12));
+        assert!(!DoubleDownRule::TenToEleven.allows(9));
+        assert!(DoubleDownRule::TenToEleven.allows(10));
+
+        assert_eq!(Ok(DoubleDownRule::NineToEleven), "nine-to-eleven".parse());
+        assert_eq!(Ok(DoubleDownRule::NineToEleven), "9-11".parse());
+        assert_eq!(Ok(DoubleDownRule::AnyTwoCards), "Any-Two-Cards".parse());
+        assert_eq!(Ok(DoubleDownRule::Never), " None".parse());
+        assert!("sometimes".parse::<DoubleDownRule>().is_err());
+
+        assert_eq!(Ok(SurrenderRule::NoSurrender), "no-surrender".parse());
+        assert_eq!(Ok(SurrenderRule::Late), "late".parse());
+        assert!("whenever".parse::<SurrenderRule>().is_err());
+    }
+
+    #[test]
+    fn checks_bet_limits() {
+        let rules = TableRules::vegas_strip();
+        assert!(rules.check_bet(0).is_ok());
+        assert!(rules.check_bet(10).is_err());
+        assert!(rules.check_bet(25).is_ok());
+        assert!(rules.check_bet(5000).is_ok());
+        assert!(rules.check_bet(5001).is_err());
+
+        assert!(TableRules::default().check_bet(1_000_000).is_ok());
+    }
+
+    #[test]
+    fn parses_presets() {
+        assert_eq!(Ok(RulesPreset::VegasStrip), "vegas-strip".parse());
+        assert_eq!(Ok(RulesPreset::AtlanticCity), "Atlantic-City".parse());
+        assert_eq!(Ok(RulesPreset::European), "european".parse());
+        assert!("monte-carlo".parse::<RulesPreset>().is_err());
+
+        let european = RulesPreset::European.rules();
+        assert_eq!(HoleCardPolicy::NoHoleCard, european.hole_card_policy);
+        assert_eq!(2, RulesPreset::Downtown.rules().num_decks);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn rules_round_trip_through_toml_and_json() {
+        for preset in [
+            RulesPreset::VegasStrip,
+            RulesPreset::AtlanticCity,
+            RulesPreset::Downtown,
+            RulesPreset::European,
+        ] {
+            let rules = preset.rules();
+
+            let toml_rules = toml::to_string(&rules).unwrap();
+            assert_eq!(rules, toml::from_str(&toml_rules).unwrap());
+
+            let json_rules = serde_json::to_string(&rules).unwrap();
+            assert_eq!(rules, serde_json::from_str(&json_rules).unwrap());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn missing_rules_use_defaults() {
+        let rules: TableRules = toml::from_str(
+            r#"
+            num_decks = 2
+            penetration = "1.5 decks"
+            dealer_hits_soft_17 = true
+            surrender_rule = "late"
+            double_down_rule = "ten-to-eleven"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            TableRules {
+                num_decks: 2,
+                penetration: Penetration::Decks(1.5),
+                dealer_hits_soft_17: true,
+                surrender_rule: SurrenderRule::Late,
+                double_down_rule: DoubleDownRule::TenToEleven,
+                ..TableRules::default()
+            },
+            rules
+        );
+
+        // Typos in rule names shouldn't be silently ignored.
+        assert!(toml::from_str::<TableRules>("num_deck = 2").is_err());
+    }
+}
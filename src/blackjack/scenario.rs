@@ -0,0 +1,237 @@
+//! Scenarios: a quick way to play out one exact round of blackjack, like "the dealer shows a 6
+//! and the player has A-7". A scenario says what everybody is dealt, and it stacks the deck
+//! so the round goes exactly that way.
+//!
+//! ```
+//! use praeses_blackjack::blackjack::actors::{dealers::StandardDealer, players::ScriptedPlayer, Action};
+//! use praeses_blackjack::blackjack::{self, PlayerRoundResult, Scenario};
+//! use praeses_blackjack::cards::Rank;
+//!
+//! struct Quiet;
+//!
+//! impl blackjack::Frontend for Quiet {
+//!     fn handle_event(&mut self, _event: &blackjack::Event) {}
+//!
+//!     fn play_another_round(&mut self) -> bool {
+//!         false
+//!     }
+//! }
+//!
+//! // A-7 against a 6: double down, catch a 3 for 21, and the dealer busts.
+//! let player = ScriptedPlayer::with_actions(100, &[Action::DoubleDown]);
+//! let outcome = Scenario::new()
+//!     .player_with_bet(player, 10, [Rank::Ace, Rank::Seven])
+//!     .dealer(Rank::Six, Rank::Ten)
+//!     .draws(&[Rank::Three, Rank::Nine])
+//!     .play::<StandardDealer>(&mut Quiet)
+//!     .unwrap();
+//!
+//! assert_eq!(vec![PlayerRoundResult::Win], outcome.results[0]);
+//! assert_eq!(Some(120), outcome.players[0].money());
+//! ```
+
+use crate::blackjack::actors::dealers::Dealer;
+use crate::blackjack::actors::players::Player;
+use crate::blackjack::{self, Frontend, HoleCardPolicy, RoundOutcome, TableRules};
+use crate::cards;
+
+/// One round of blackjack set up card by card. Cards are given by rank, since suits don't
+/// matter in blackjack.
+pub struct Scenario {
+    rules: TableRules,
+    seats: Vec<(Box<dyn Player>, [cards::Rank; 2])>,
+    dealer_upcard: Option<cards::Rank>,
+    dealer_hole_card: Option<cards::Rank>,
+    draws: Vec<cards::Rank>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Scenario::new()
+    }
+}
+
+impl Scenario {
+    /// Starts a scenario at a table with the default rules, without anybody seated yet.
+    pub fn new() -> Scenario {
+        Scenario {
+            rules: TableRules::default(),
+            seats: Vec::new(),
+            dealer_upcard: None,
+            dealer_hole_card: None,
+            draws: Vec::new(),
+        }
+    }
+
+    /// Plays the scenario with the given table rules.
+    pub fn rules(mut self, rules: TableRules) -> Scenario {
+        self.rules = rules;
+        self
+    }
+
+    /// Seats a player who is dealt the given two cards. Players are seated (and dealt to) in the order they're added.
+    pub fn player(mut self, player: impl Player + 'static, cards: [cards::Rank; 2]) -> Scenario {
+        self.seats.push((Box::new(player), cards));
+        self
+    }
+
+    /// Seats a player just like [`Scenario::player`], with a bet taken out of their money.
+    /// Meant for players who don't place bets themselves, like bots.
+    ///
+    /// # Panics
+    ///
+    /// If the player doesn't have enough money to cover the bet.
+    pub fn player_with_bet(
+        self,
+        mut player: impl Player + 'static,
+        bet: u32,
+        cards: [cards::Rank; 2],
+    ) -> Scenario {
+        let money = player
+            .money()
+            .filter(|&money| money >= bet)
+            .expect("Players need enough money to cover their bet in a scenario");
+        *player.money_mut() = Some(money - bet);
+        *player.bet_mut() = Some(bet);
+        self.player(player, cards)
+    }
+
+    /// Deals the dealer the given face-up card and hole card.
+    pub fn dealer(mut self, upcard: cards::Rank, hole_card: cards::Rank) -> Scenario {
+        self.dealer_upcard = Some(upcard);
+        self.dealer_hole_card = Some(hole_card);
+        self
+    }
+
+    /// Deals the dealer just their face-up card. This is for tables without a hole card,
+    /// where the dealer's second card comes after the players are done, so it goes in the draws.
+    pub fn dealer_upcard(mut self, upcard: cards::Rank) -> Scenario {
+        self.dealer_upcard = Some(upcard);
+        self.dealer_hole_card = None;
+        self
+    }
+
+    /// Sets the cards dealt after the first two cards, in the order they come out:
+    /// every card the players hit, double, or split for, and then the dealer's.
+    pub fn draws(mut self, draws: &[cards::Rank]) -> Scenario {
+        self.draws = draws.to_vec();
+        self
+    }
+
+    /// Stacks a deck that deals exactly the cards in this scenario. The top of the deck is its end.
+    pub fn stacked_deck(&self) -> Result<cards::Deck, &'static str> {
+        let upcard = self
+            .dealer_upcard
+            .ok_or("The dealer needs an upcard in a scenario")?;
+        let peek = self.rules.hole_card_policy == HoleCardPolicy::Peek;
+        let hole_card = match (peek, self.dealer_hole_card) {
+            (true, Some(hole_card)) => Some(hole_card),
+            (true, None) => return Err("The dealer needs a hole card at a table where they peek"),
+            (false, Some(_)) => {
+                return Err("Without a hole card, the dealer's second card comes from the draws")
+            }
+            (false, None) => None,
+        };
+
+        // Everybody gets a card, then everybody gets a second one. With a hole card, the
+        // dealer's first card is the hole card.
+        let mut deal_order: Vec<cards::Rank> = Vec::new();
+        for round in 0..2 {
+            deal_order.extend(self.seats.iter().map(|(_, cards)| cards[round]));
+            match (round, hole_card) {
+                (0, Some(hole_card)) => deal_order.push(hole_card),
+                (0, None) | (1, Some(_)) => deal_order.push(upcard),
+                _ => {}
+            }
+        }
+        deal_order.extend(&self.draws);
+
+        Ok(deal_order
+            .into_iter()
+            .rev()
+            .map(|rank| cards::Card {
+                rank,
+                suit: cards::Suit::Spade,
+            })
+            .collect())
+    }
+
+    /// Plays the scenario's round, telling the front end everything that happens.
+    /// Returns an error if the scenario is missing the dealer's cards. If the round needs more
+    /// cards than the scenario deals, hands that would take another card stand instead.
+    pub fn play<D: Dealer>(
+        self,
+        frontend: &mut dyn Frontend,
+    ) -> Result<RoundOutcome, &'static str> {
+        let deck = self.stacked_deck()?;
+        let players = self.seats.into_iter().map(|(player, _)| player).collect();
+        Ok(blackjack::play_round_with_shoe::<D>(
+            players,
+            &self.rules,
+            frontend,
+            cards::Shoe::stacked(deck),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::actors::players::AutoPlayer;
+    use cards::Rank;
+
+    fn ranks(deck: &[cards::Card]) -> Vec<Rank> {
+        deck.iter().rev().map(|card| card.rank).collect()
+    }
+
+    #[test]
+    fn stacks_deck_in_deal_order() {
+        let scenario = Scenario::new()
+            .player(AutoPlayer::new(0), [Rank::Ace, Rank::Seven])
+            .player(AutoPlayer::new(0), [Rank::Two, Rank::Three])
+            .dealer(Rank::Six, Rank::King)
+            .draws(&[Rank::Four]);
+
+        assert_eq!(
+            vec![
+                Rank::Ace,
+                Rank::Two,
+                Rank::King,
+                Rank::Seven,
+                Rank::Three,
+                Rank::Six,
+                Rank::Four
+            ],
+            ranks(&scenario.stacked_deck().unwrap())
+        );
+    }
+
+    #[test]
+    fn no_hole_card_deals_upcard_first() {
+        let scenario = Scenario::new()
+            .rules(TableRules::european())
+            .player(AutoPlayer::new(0), [Rank::Ten, Rank::Nine])
+            .dealer_upcard(Rank::Ace)
+            .draws(&[Rank::King]);
+
+        assert_eq!(
+            vec![Rank::Ten, Rank::Ace, Rank::Nine, Rank::King],
+            ranks(&scenario.stacked_deck().unwrap())
+        );
+    }
+
+    #[test]
+    fn needs_the_dealers_cards() {
+        let scenario = Scenario::new().player(AutoPlayer::new(0), [Rank::Ten, Rank::Nine]);
+        assert!(scenario.stacked_deck().is_err());
+
+        let scenario = scenario.dealer_upcard(Rank::Six);
+        assert!(scenario.stacked_deck().is_err());
+
+        let scenario = scenario.rules(TableRules::european());
+        assert!(scenario.stacked_deck().is_ok());
+
+        let scenario = scenario.dealer(Rank::Six, Rank::Ten);
+        assert!(scenario.stacked_deck().is_err());
+    }
+}
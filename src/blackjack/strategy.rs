@@ -0,0 +1,571 @@
+//! Strategy charts: what to do with every hand against every dealer upcard, laid out the way
+//! the charts on casino strategy cards are. There's a row for each hard total, soft total, and
+//! pair, and a column for each upcard from 2 through ace.
+//!
+//! [`Chart::basic_strategy`] works out basic strategy (the best way to play without counting
+//! cards) for a table's rules, since the right play changes with the number of decks, whether
+//! the dealer hits soft 17, and what doubling, splitting and surrender are allowed.
+//!
+//! ```
+//! use praeses_blackjack::blackjack::strategy::{Chart, Play};
+//! use praeses_blackjack::blackjack::TableRules;
+//!
+//! let chart = Chart::basic_strategy(&TableRules::default());
+//! assert_eq!(Play::DoubleOrHit, chart.hard(11, 6));
+//! assert_eq!(Play::Split, chart.pair(8, 10));
+//! ```
+//!
+//! Charts can also be loaded from CSV or TOML files with [`Chart::from_file`], and card
+//! counters can change their play with the count using a [`DeviationTable`].
+
+use std::fmt;
+use std::str::FromStr;
+
+mod chart_file;
+mod deviations;
+pub use chart_file::ChartError;
+pub use deviations::{Deviation, DeviationTable, IndexDirection};
+
+use crate::blackjack::actors::Action;
+use crate::blackjack::{self, HoleCardPolicy, SurrenderRule, TableRules};
+use crate::cards;
+
+/// What a chart says to do with a hand. Some plays fall back to another action when the first
+/// choice isn't allowed, like doubling down on a third card.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Play {
+    Hit,
+    Stand,
+    /// Double down if allowed, otherwise hit.
+    DoubleOrHit,
+    /// Double down if allowed, otherwise stand.
+    DoubleOrStand,
+    /// Split the pair.
+    Split,
+    /// Surrender if allowed, otherwise hit.
+    SurrenderOrHit,
+    /// Surrender if allowed, otherwise stand.
+    SurrenderOrStand,
+    /// Surrender if allowed, otherwise split.
+    SurrenderOrSplit,
+}
+
+impl Play {
+    /// Given a play, return the short code used for it on strategy charts.
+    const fn code(&self) -> &str {
+        match self {
+            Self::Hit => "H",
+            Self::Stand => "S",
+            Self::DoubleOrHit => "Dh",
+            Self::DoubleOrStand => "Ds",
+            Self::Split => "P",
+            Self::SurrenderOrHit => "Rh",
+            Self::SurrenderOrStand => "Rs",
+            Self::SurrenderOrSplit => "Rp",
+        }
+    }
+
+    /// Gets the play that surrenders if allowed and otherwise makes this play.
+    const fn surrender(self) -> Play {
+        match self {
+            Self::Stand | Self::DoubleOrStand | Self::SurrenderOrStand => Self::SurrenderOrStand,
+            Self::Split | Self::SurrenderOrSplit => Self::SurrenderOrSplit,
+            _ => Self::SurrenderOrHit,
+        }
+    }
+
+    /// Returns true if the play might split, which only makes sense for a pair.
+    const fn splits(&self) -> bool {
+        matches!(self, Self::Split | Self::SurrenderOrSplit)
+    }
+
+    /// Picks the action to take for this play out of the allowed actions. Charts only say to
+    /// split pairs that can be split, but if splitting isn't allowed the hand is hit instead,
+    /// and a hand that can't be hit (like split aces) stands.
+    pub fn action(&self, allowed_actions: &[Action]) -> Action {
+        let (first_choice, fallback) = match self {
+            Self::Hit => (Action::Hit, Action::Hit),
+            Self::Stand => (Action::Stand, Action::Stand),
+            Self::DoubleOrHit => (Action::DoubleDown, Action::Hit),
+            Self::DoubleOrStand => (Action::DoubleDown, Action::Stand),
+            Self::Split => (Action::Split, Action::Hit),
+            Self::SurrenderOrHit => (Action::Surrender, Action::Hit),
+            Self::SurrenderOrStand => (Action::Surrender, Action::Stand),
+            Self::SurrenderOrSplit => (Action::Surrender, Action::Split),
+        };
+
+        if allowed_actions.contains(&first_choice) {
+            first_choice
+        } else if first_choice == Action::Surrender && fallback == Action::Split {
+            Self::Split.action(allowed_actions)
+        } else if allowed_actions.contains(&fallback) {
+            fallback
+        } else {
+            Action::Stand
+        }
+    }
+}
+
+impl FromStr for Play {
+    type Err = &'static str;
+
+    /// Parses a play from its code on a strategy chart, like "H" or "Ds". A plain "D" means
+    /// double down or hit, and "R" means surrender or hit.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "h" => Ok(Self::Hit),
+            "s" => Ok(Self::Stand),
+            "d" | "dh" => Ok(Self::DoubleOrHit),
+            "ds" => Ok(Self::DoubleOrStand),
+            "p" | "sp" => Ok(Self::Split),
+            "r" | "rh" => Ok(Self::SurrenderOrHit),
+            "rs" => Ok(Self::SurrenderOrStand),
+            "rp" => Ok(Self::SurrenderOrSplit),
+            _ => Err("Invalid play (try H, S, Dh, Ds, P, Rh, Rs, or Rp)"),
+        }
+    }
+}
+
+impl fmt::Display for Play {
+    /// Shows the play's code from strategy charts, like "Ds".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.code())
+    }
+}
+
+/// How many dealer upcards there are: 2 through 10, and ace.
+pub const UPCARDS: usize = 10;
+/// The lowest hard total on a chart (a pair of twos that can't be split).
+pub const LOWEST_HARD_TOTAL: u32 = 4;
+/// The lowest soft total on a chart (a pair of aces that can't be split).
+pub const LOWEST_SOFT_TOTAL: u32 = 12;
+
+/// Which row of a chart a hand is played from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ChartHand {
+    /// A hard total from 4 to 21.
+    Hard(u32),
+    /// A soft total from 12 to 21.
+    Soft(u32),
+    /// A pair of cards worth this much each (2 through 11 for aces).
+    Pair(u32),
+}
+
+impl ChartHand {
+    /// Works out which row a hand is played from. Pairs use the pair rows if they can be
+    /// split, and otherwise every hand is played by its total.
+    pub fn of(hand: &[cards::Card], allowed_actions: &[Action]) -> ChartHand {
+        if blackjack::hand_is_pair(hand) && allowed_actions.contains(&Action::Split) {
+            return ChartHand::Pair(chart_value(&hand[0]));
+        }
+        let raw_value = blackjack::raw_hand_value(hand);
+        if blackjack::is_soft_hand(raw_value, hand) {
+            ChartHand::Soft(raw_value + 10)
+        } else {
+            ChartHand::Hard(raw_value)
+        }
+    }
+}
+
+/// A row of a chart, with a play for each dealer upcard.
+pub type Row = [Play; UPCARDS];
+
+/// A strategy chart. Totals and card values count aces as 11, so the last column is the
+/// dealer's ace and the last pair is a pair of aces.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chart {
+    /// Hard totals from 4 to 21.
+    hard: [Row; 18],
+    /// Soft totals from 12 (two aces) to 21.
+    soft: [Row; 10],
+    /// Pairs from twos up to tens, and then aces. Plays that aren't splits are how to play
+    /// the pair as a hard or soft total.
+    pairs: [Row; 10],
+}
+
+impl Chart {
+    /// Creates a chart from its rows. Rows are in order from the lowest hand up: hard totals
+    /// from 4 to 21, soft totals from 12 to 21, and pairs from twos to tens and then aces.
+    pub fn new(hard: [Row; 18], soft: [Row; 10], pairs: [Row; 10]) -> Chart {
+        Chart { hard, soft, pairs }
+    }
+
+    /// Works out basic strategy for the given table rules. It's the same strategy as the
+    /// published charts for one, two, and four or more decks, with changes for whether the
+    /// dealer hits soft 17, doubling after splits, surrender, and European no hole card games.
+    pub fn basic_strategy(rules: &TableRules) -> Chart {
+        let strategy = BasicStrategy::new(rules);
+        Chart {
+            hard: rows(LOWEST_HARD_TOTAL, |total, upcard| {
+                strategy.hard(total, upcard)
+            }),
+            soft: rows(LOWEST_SOFT_TOTAL, |total, upcard| {
+                strategy.soft(total, upcard)
+            }),
+            pairs: rows(2, |value, upcard| strategy.pair(value, upcard)),
+        }
+    }
+
+    /// What to do with a hard total against the dealer's upcard (2 through 11 for an ace).
+    /// Totals below 4 are played like 4 and totals above 21 like 21.
+    pub fn hard(&self, total: u32, upcard: u32) -> Play {
+        let row = total.clamp(LOWEST_HARD_TOTAL, 21) - LOWEST_HARD_TOTAL;
+        self.hard[row as usize][column(upcard)]
+    }
+
+    /// What to do with a soft total against the dealer's upcard (2 through 11 for an ace).
+    pub fn soft(&self, total: u32, upcard: u32) -> Play {
+        let row = total.clamp(LOWEST_SOFT_TOTAL, 21) - LOWEST_SOFT_TOTAL;
+        self.soft[row as usize][column(upcard)]
+    }
+
+    /// What to do with a pair of cards worth `value` each (2 through 11 for aces) against
+    /// the dealer's upcard (2 through 11 for an ace).
+    pub fn pair(&self, value: u32, upcard: u32) -> Play {
+        let row = value.clamp(2, 11) - 2;
+        self.pairs[row as usize][column(upcard)]
+    }
+
+    /// What to do with a hand from any row of the chart against the dealer's upcard (2
+    /// through 11 for an ace).
+    pub fn play(&self, hand: ChartHand, upcard: u32) -> Play {
+        match hand {
+            ChartHand::Hard(total) => self.hard(total, upcard),
+            ChartHand::Soft(total) => self.soft(total, upcard),
+            ChartHand::Pair(value) => self.pair(value, upcard),
+        }
+    }
+
+    /// Decides what to do with a hand against the dealer's upcard, out of the allowed actions.
+    /// Pairs use the pair rows if they can be split, and otherwise every hand is played by
+    /// its total.
+    pub fn decide(
+        &self,
+        hand: &[cards::Card],
+        dealer_upcard: &cards::Card,
+        allowed_actions: &[Action],
+    ) -> Action {
+        let play = self.play(
+            ChartHand::of(hand, allowed_actions),
+            chart_value(dealer_upcard),
+        );
+        play.action(allowed_actions)
+    }
+}
+
+/// Gets a card's value the way charts write it, with aces as 11.
+fn chart_value(card: &cards::Card) -> u32 {
+    match blackjack::card_value(card) {
+        1 => 11,
+        value => value,
+    }
+}
+
+/// Gets the chart column for a dealer upcard (2 through 11 for an ace).
+fn column(upcard: u32) -> usize {
+    (upcard.clamp(2, 11) - 2) as usize
+}
+
+/// Builds chart rows, one for each hand starting from `first`, with the play for each upcard.
+fn rows<const N: usize>(first: u32, play: impl Fn(u32, u32) -> Play) -> [Row; N] {
+    let mut rows = [[Play::Hit; UPCARDS]; N];
+    for (hand, row) in (first..).zip(rows.iter_mut()) {
+        for (upcard, cell) in (2..).zip(row.iter_mut()) {
+            *cell = play(hand, upcard);
+        }
+    }
+    rows
+}
+
+/// The parts of the table rules that basic strategy depends on. Upcards go from 2 to 11 (an ace).
+struct BasicStrategy {
+    num_decks: u32,
+    dealer_hits_soft_17: bool,
+    double_after_split: bool,
+    surrender: bool,
+    no_hole_card: bool,
+}
+
+impl BasicStrategy {
+    fn new(rules: &TableRules) -> BasicStrategy {
+        BasicStrategy {
+            num_decks: rules.num_decks,
+            dealer_hits_soft_17: rules.dealer_hits_soft_17,
+            double_after_split: rules.double_after_split,
+            surrender: rules.surrender_rule != SurrenderRule::NoSurrender,
+            no_hole_card: rules.hole_card_policy == HoleCardPolicy::NoHoleCard,
+        }
+    }
+
+    fn hard(&self, total: u32, upcard: u32) -> Play {
+        let double_if = |double: bool| if double { Play::DoubleOrHit } else { Play::Hit };
+        let stand_if = |stand: bool| if stand { Play::Stand } else { Play::Hit };
+
+        if self.surrender {
+            let surrender = match total {
+                15 => upcard == 10 || (upcard == 11 && self.dealer_hits_soft_17),
+                16 => upcard >= 10 || (upcard == 9 && self.num_decks >= 4),
+                17 => upcard == 11 && self.dealer_hits_soft_17,
+                _ => false,
+            };
+            if surrender {
+                return stand_if(total >= 17).surrender();
+            }
+        }
+
+        match total {
+            0..=7 => Play::Hit,
+            8 => double_if(self.num_decks == 1 && (5..=6).contains(&upcard)),
+            9 => double_if((3..=6).contains(&upcard) || (upcard == 2 && self.num_decks <= 2)),
+            10 => double_if(upcard <= 9),
+            11 => double_if(match upcard {
+                10 => !self.no_hole_card,
+                11 => !self.no_hole_card && (self.num_decks <= 2 || self.dealer_hits_soft_17),
+                _ => true,
+            }),
+            12 => stand_if((4..=6).contains(&upcard)),
+            13..=16 => stand_if(upcard <= 6),
+            _ => Play::Stand,
+        }
+    }
+
+    fn soft(&self, total: u32, upcard: u32) -> Play {
+        let double_if = |double: bool| if double { Play::DoubleOrHit } else { Play::Hit };
+        let single_deck = self.num_decks == 1;
+
+        match total {
+            13 | 14 => double_if((5..=6).contains(&upcard) || (upcard == 4 && single_deck)),
+            15 | 16 => double_if((4..=6).contains(&upcard)),
+            17 => double_if((3..=6).contains(&upcard) || (upcard == 2 && single_deck)),
+            18 => match upcard {
+                3..=6 => Play::DoubleOrStand,
+                2 if self.dealer_hits_soft_17 => Play::DoubleOrStand,
+                2..=8 => Play::Stand,
+                11 if single_deck && !self.dealer_hits_soft_17 => Play::Stand,
+                _ => Play::Hit,
+            },
+            19 if upcard == 6 && (single_deck || self.dealer_hits_soft_17) => Play::DoubleOrStand,
+            0..=12 => Play::Hit,
+            _ => Play::Stand,
+        }
+    }
+
+    fn pair(&self, value: u32, upcard: u32) -> Play {
+        let das = self.double_after_split;
+        let few_decks = self.num_decks <= 2;
+        let split = match value {
+            2 | 3 if das => upcard <= 7 || (value == 3 && upcard == 8 && self.num_decks == 1),
+            2 | 3 => (4..=7).contains(&upcard),
+            4 if das => (5..=6).contains(&upcard) || (upcard == 4 && self.num_decks == 1),
+            6 if das => upcard <= 6 || (upcard == 7 && few_decks),
+            6 => (3..=6).contains(&upcard) || (upcard == 2 && few_decks),
+            7 => upcard <= 7 || (upcard == 8 && few_decks),
+            8 => !(self.no_hole_card && upcard >= 10),
+            9 => upcard <= 9 && upcard != 7,
+            11 => !(self.no_hole_card && upcard == 11),
+            _ => false,
+        };
+
+        if !split {
+            return if value == 11 {
+                self.soft(12, upcard)
+            } else {
+                self.hard(value * 2, upcard)
+            };
+        }
+        if value == 8 && upcard == 11 && self.surrender && self.dealer_hits_soft_17 && !few_decks {
+            return Play::SurrenderOrSplit;
+        }
+        Play::Split
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads chart rows written out like a strategy card, one code per upcard from 2 to ace.
+    fn chart_rows(rows: &[&str]) -> Vec<Vec<Play>> {
+        rows.iter()
+            .map(|row| {
+                row.split_whitespace()
+                    .map(|code| code.parse().unwrap())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Checks the rows of a chart against the expected rows, starting from the given hand.
+    fn assert_rows(expected: &[&str], first: u32, actual: impl Fn(u32, u32) -> Play) {
+        for (hand, row) in (first..).zip(chart_rows(expected)) {
+            let actual_row: Vec<Play> = (2..=11).map(|upcard| actual(hand, upcard)).collect();
+            assert_eq!(row, actual_row, "row for {}", hand);
+        }
+    }
+
+    #[test]
+    fn matches_published_multi_deck_chart() {
+        // Four to eight decks, dealer stands on soft 17, double after split, late surrender.
+        let chart = Chart::basic_strategy(&TableRules::vegas_strip());
+
+        assert_rows(
+            &[
+                "H  H  H  H  H  H  H  H  H  H",
+                "H  Dh Dh Dh Dh H  H  H  H  H",
+                "Dh Dh Dh Dh Dh Dh Dh Dh H  H",
+                "Dh Dh Dh Dh Dh Dh Dh Dh Dh H",
+                "H  H  S  S  S  H  H  H  H  H",
+                "S  S  S  S  S  H  H  H  H  H",
+                "S  S  S  S  S  H  H  H  H  H",
+                "S  S  S  S  S  H  H  H  Rh H",
+                "S  S  S  S  S  H  H  Rh Rh Rh",
+                "S  S  S  S  S  S  S  S  S  S",
+            ],
+            8,
+            |total, upcard| chart.hard(total, upcard),
+        );
+        assert_rows(
+            &[
+                "H  H  H  Dh Dh H  H  H  H  H",
+                "H  H  H  Dh Dh H  H  H  H  H",
+                "H  H  Dh Dh Dh H  H  H  H  H",
+                "H  H  Dh Dh Dh H  H  H  H  H",
+                "H  Dh Dh Dh Dh H  H  H  H  H",
+                "S  Ds Ds Ds Ds S  S  H  H  H",
+                "S  S  S  S  S  S  S  S  S  S",
+                "S  S  S  S  S  S  S  S  S  S",
+            ],
+            13,
+            |total, upcard| chart.soft(total, upcard),
+        );
+        assert_rows(
+            &[
+                "P  P  P  P  P  P  H  H  H  H",
+                "P  P  P  P  P  P  H  H  H  H",
+                "H  H  H  P  P  H  H  H  H  H",
+                "Dh Dh Dh Dh Dh Dh Dh Dh H  H",
+                "P  P  P  P  P  H  H  H  H  H",
+                "P  P  P  P  P  P  H  H  H  H",
+                "P  P  P  P  P  P  P  P  P  P",
+                "P  P  P  P  P  S  P  P  S  S",
+                "S  S  S  S  S  S  S  S  S  S",
+                "P  P  P  P  P  P  P  P  P  P",
+            ],
+            2,
+            |value, upcard| chart.pair(value, upcard),
+        );
+    }
+
+    #[test]
+    fn follows_the_dealer_hitting_soft_17() {
+        let rules = TableRules {
+            dealer_hits_soft_17: true,
+            ..TableRules::vegas_strip()
+        };
+        let chart = Chart::basic_strategy(&rules);
+
+        assert_eq!(Play::DoubleOrHit, chart.hard(11, 11));
+        assert_eq!(Play::SurrenderOrHit, chart.hard(15, 11));
+        assert_eq!(Play::SurrenderOrStand, chart.hard(17, 11));
+        assert_eq!(Play::SurrenderOrSplit, chart.pair(8, 11));
+        assert_eq!(Play::DoubleOrStand, chart.soft(18, 2));
+        assert_eq!(Play::DoubleOrStand, chart.soft(19, 6));
+    }
+
+    #[test]
+    fn follows_deck_count_and_splitting_rules() {
+        let single_deck = Chart::basic_strategy(&TableRules {
+            num_decks: 1,
+            ..TableRules::default()
+        });
+        assert_eq!(Play::DoubleOrHit, single_deck.hard(8, 5));
+        assert_eq!(Play::DoubleOrHit, single_deck.hard(9, 2));
+        assert_eq!(Play::DoubleOrHit, single_deck.hard(11, 11));
+        assert_eq!(Play::Stand, single_deck.soft(18, 11));
+        assert_eq!(Play::DoubleOrStand, single_deck.soft(19, 6));
+
+        let no_das = Chart::basic_strategy(&TableRules {
+            double_after_split: false,
+            ..TableRules::default()
+        });
+        assert_eq!(Play::Hit, no_das.pair(2, 2));
+        assert_eq!(Play::Hit, no_das.pair(4, 5));
+        assert_eq!(Play::Hit, no_das.pair(6, 2));
+        assert_eq!(Play::Split, no_das.pair(6, 3));
+
+        // Without a hole card, don't put more money out against a possible blackjack.
+        let european = Chart::basic_strategy(&TableRules::european());
+        assert_eq!(Play::Hit, european.hard(11, 10));
+        assert_eq!(Play::Hit, european.pair(8, 10));
+        assert_eq!(Play::Hit, european.pair(11, 11));
+    }
+
+    #[test]
+    fn decides_with_the_allowed_actions() {
+        let chart = Chart::basic_strategy(&TableRules::vegas_strip());
+        let hand = |text: &str| -> cards::Hand {
+            text.parse::<cards::notation::ParsedHand>().unwrap().into()
+        };
+        let upcard = |text: &str| text.parse::<cards::Card>().unwrap();
+        let everything = [
+            Action::Hit,
+            Action::Stand,
+            Action::DoubleDown,
+            Action::Split,
+            Action::Surrender,
+        ];
+        let hit_or_stand = [Action::Hit, Action::Stand];
+
+        assert_eq!(
+            Action::DoubleDown,
+            chart.decide(&hand("A7"), &upcard("6c"), &everything)
+        );
+        assert_eq!(
+            Action::Stand,
+            chart.decide(&hand("A7"), &upcard("6c"), &hit_or_stand)
+        );
+        assert_eq!(
+            Action::Hit,
+            chart.decide(&hand("A52"), &upcard("Ts"), &hit_or_stand)
+        );
+        assert_eq!(
+            Action::Split,
+            chart.decide(&hand("88"), &upcard("Ts"), &everything)
+        );
+        assert_eq!(
+            Action::Surrender,
+            chart.decide(&hand("T6"), &upcard("Ts"), &everything)
+        );
+        assert_eq!(
+            Action::Hit,
+            chart.decide(&hand("T6"), &upcard("Ts"), &hit_or_stand)
+        );
+        // A pair that can't be split anymore is played by its total.
+        assert_eq!(
+            Action::Hit,
+            chart.decide(&hand("AA"), &upcard("6c"), &hit_or_stand)
+        );
+        assert_eq!(
+            Action::Stand,
+            chart.decide(&hand("KJ"), &upcard("6c"), &everything)
+        );
+        // Plays that split fall back to hitting (or standing, if the hand can't be hit) when
+        // splitting isn't allowed, rather than asking for a split that would be rejected.
+        assert_eq!(Action::Hit, Play::Split.action(&hit_or_stand));
+        assert_eq!(Action::Hit, Play::SurrenderOrSplit.action(&hit_or_stand));
+        assert_eq!(Action::Stand, Play::Split.action(&[Action::Stand]));
+        assert_eq!(
+            Action::Split,
+            Play::SurrenderOrSplit.action(&[Action::Stand, Action::Split])
+        );
+    }
+
+    #[test]
+    fn parses_play_codes() {
+        assert_eq!(Ok(Play::DoubleOrHit), "D".parse());
+        assert_eq!(Ok(Play::DoubleOrStand), "ds".parse());
+        assert_eq!(Ok(Play::SurrenderOrSplit), "Rp".parse());
+        assert!("X".parse::<Play>().is_err());
+        assert_eq!("Rh", Play::SurrenderOrHit.to_string());
+    }
+}
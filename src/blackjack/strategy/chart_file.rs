@@ -0,0 +1,670 @@
+//! Reading and writing strategy charts as files, so custom charts can be kept alongside the
+//! code. Charts are written as CSV or (with the `serde` feature) TOML.
+//!
+//! In a CSV chart, the first row has the dealer upcards (2 through 9, then T or 10, and A)
+//! and every other row starts with the hand it's for:
+//!
+//! * Hard totals are written like `H12` or just `12`.
+//! * Soft totals are written like `S18` or `A7`.
+//! * Pairs are written like `P8` or `88`, with `TT` for tens and `AA` for aces.
+//!
+//! Totals can cover several rows at once, like `H4-8` or `H17+`. Blank lines and lines
+//! starting with `#` are skipped.
+//!
+//! ```text
+//! hand, 2,  3,  4,  5,  6,  7,  8,  9,  T,  A
+//! H4-8, H,  H,  H,  H,  H,  H,  H,  H,  H,  H
+//! H9,   H,  Dh, Dh, Dh, Dh, H,  H,  H,  H,  H
+//! ...
+//! ```
+//!
+//! In a TOML chart, the rows are in `[hard]`, `[soft]`, and `[pairs]` tables, keyed by total
+//! (or by the pair's card), with the plays for upcards 2 through ace in order:
+//!
+//! ```toml
+//! [hard]
+//! "4-8" = "H H H H H H H H H H"
+//! "9" = "H Dh Dh Dh Dh H H H H H"
+//! ```
+//!
+//! Every cell of the chart has to be filled in, so a chart always knows what to do.
+
+use std::fmt;
+use std::fs;
+use std::io;
+use std::ops::RangeInclusive;
+use std::path::Path;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
+
+use crate::blackjack::strategy::{
+    column, Chart, ChartHand, Play, Row, LOWEST_HARD_TOTAL, LOWEST_SOFT_TOTAL, UPCARDS,
+};
+use crate::cards;
+
+/// Errors that can happen when loading a strategy chart.
+#[derive(Debug)]
+pub enum ChartError {
+    /// The chart file couldn't be read.
+    Io(io::Error),
+    /// The chart file wasn't valid TOML.
+    #[cfg(feature = "serde")]
+    Toml(toml::de::Error),
+    /// Part of the chart couldn't be understood.
+    Invalid(String),
+    /// The chart doesn't say what to do in some situations. Holds every missing cell, like
+    /// "hard 12 against 4".
+    MissingCells(Vec<String>),
+}
+
+impl fmt::Display for ChartError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChartError::Io(e) => write!(f, "Couldn't read chart file: {}", e),
+            #[cfg(feature = "serde")]
+            ChartError::Toml(e) => write!(f, "Invalid TOML chart file: {}", e),
+            ChartError::Invalid(message) => write!(f, "Invalid chart: {}", message),
+            ChartError::MissingCells(cells) => write!(
+                f,
+                "The chart is missing {} cell(s), starting with {}",
+                cells.len(),
+                cells[0]
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ChartError {}
+
+/// The hands a row of a chart is for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum RowHands {
+    /// Hard totals from the first to the last.
+    Hard(u32, u32),
+    /// Soft totals from the first to the last.
+    Soft(u32, u32),
+    /// A pair of cards worth this much each (11 for aces).
+    Pair(u32),
+}
+
+impl RowHands {
+    /// Parses the label at the start of a row in a CSV chart.
+    fn parse(label: &str) -> Result<RowHands, String> {
+        let label = label.trim().to_uppercase();
+        if let Some(totals) = label.strip_prefix('H') {
+            return RowHands::parse_hard(totals);
+        }
+        if let Some(totals) = label.strip_prefix('S') {
+            return RowHands::parse_soft(totals);
+        }
+        if let Some(card) = label.strip_prefix('P') {
+            return RowHands::parse_pair(card);
+        }
+
+        // Without a prefix, numbers are hard totals, both cards are a pair, and an ace and
+        // another card are a soft total.
+        if let Ok(hard) = RowHands::parse_hard(&label) {
+            return Ok(hard);
+        }
+        let (first, second) = label.split_at(label.len() / 2);
+        if !first.is_empty() && first == second {
+            return RowHands::parse_pair(first);
+        }
+        if label.starts_with('A') {
+            return RowHands::parse_soft(&label);
+        }
+        Err(format!("{} isn't a hand (try H12, S18, A7, or 88)", label))
+    }
+
+    /// Parses hard totals, like "12", "4-8", or "17+".
+    fn parse_hard(totals: &str) -> Result<RowHands, String> {
+        let (first, last) = parse_totals(totals, LOWEST_HARD_TOTAL)?;
+        Ok(RowHands::Hard(first, last))
+    }
+
+    /// Parses soft totals, like "18", "19+", or "A7" (an ace and a seven).
+    fn parse_soft(totals: &str) -> Result<RowHands, String> {
+        let totals = totals.trim();
+        if let Some(card) = totals.strip_prefix('A') {
+            let total = 11 + card_value(card)?;
+            return match total {
+                13..=21 => Ok(RowHands::Soft(total, total)),
+                _ => Err(format!("A{} isn't a soft total", card)),
+            };
+        }
+        let (first, last) = parse_totals(totals, LOWEST_SOFT_TOTAL)?;
+        Ok(RowHands::Soft(first, last))
+    }
+
+    /// Parses a pair, written as its card (like "8" or "A") or both cards (like "88" or "AA").
+    fn parse_pair(card: &str) -> Result<RowHands, String> {
+        let card = card.trim();
+        let half = card.len() / 2;
+        let card = match card.split_at(half) {
+            (first, second) if !first.is_empty() && first == second => first,
+            _ => card,
+        };
+        Ok(RowHands::Pair(card_value(card)?))
+    }
+
+    /// The totals (or pair card values) the row is for.
+    fn totals(&self) -> RangeInclusive<u32> {
+        match *self {
+            RowHands::Hard(first, last) | RowHands::Soft(first, last) => first..=last,
+            RowHands::Pair(value) => value..=value,
+        }
+    }
+
+    /// Describes one hand in the row, like "hard 12" or "a pair of 8s".
+    fn describe(&self, total: u32) -> String {
+        match self {
+            RowHands::Hard(..) => format!("hard {}", total),
+            RowHands::Soft(..) => format!("soft {}", total),
+            RowHands::Pair(_) => format!("a pair of {}s", upcard_label(total)),
+        }
+    }
+}
+
+impl FromStr for ChartHand {
+    type Err = String;
+
+    /// Parses a hand the way rows are labelled in chart files, like "H12", "A7" or "88".
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match RowHands::parse(input)? {
+            RowHands::Hard(first, last) if first == last => Ok(ChartHand::Hard(first)),
+            RowHands::Soft(first, last) if first == last => Ok(ChartHand::Soft(first)),
+            RowHands::Pair(value) => Ok(ChartHand::Pair(value)),
+            _ => Err(format!("{} is more than one hand", input.trim())),
+        }
+    }
+}
+
+/// Parses a total or range of totals, like "12", "4-8", or "17+" (which goes up to 21).
+fn parse_totals(totals: &str, lowest: u32) -> Result<(u32, u32), String> {
+    let invalid = || format!("{} isn't a total from {} to 21", totals, lowest);
+    let parse = |total: &str| {
+        total
+            .trim()
+            .parse::<u32>()
+            .ok()
+            .filter(|total| (lowest..=21).contains(total))
+            .ok_or_else(invalid)
+    };
+
+    let (first, last) = if let Some(first) = totals.strip_suffix('+') {
+        (parse(first)?, 21)
+    } else if let Some((first, last)) = totals.split_once('-') {
+        (parse(first)?, parse(last)?)
+    } else {
+        let total = parse(totals)?;
+        (total, total)
+    };
+
+    if first > last {
+        return Err(invalid());
+    }
+    Ok((first, last))
+}
+
+/// Parses a card's rank and gets its value on a chart, with aces as 11.
+pub(super) fn card_value(rank: &str) -> Result<u32, String> {
+    let rank: cards::Rank = rank
+        .parse()
+        .map_err(|_| format!("{} isn't a card", rank.trim()))?;
+    Ok(super::chart_value(&cards::Card {
+        rank,
+        suit: cards::Suit::Spade,
+    }))
+}
+
+/// Gets how an upcard (or pair card) is written on a chart, like "7", "T" or "A".
+fn upcard_label(value: u32) -> &'static str {
+    const LABELS: [&str; UPCARDS] = ["2", "3", "4", "5", "6", "7", "8", "9", "T", "A"];
+    LABELS[column(value)]
+}
+
+/// Parses the plays in a row, one for each upcard.
+fn parse_plays<'a>(cells: impl Iterator<Item = &'a str>) -> Result<Vec<Option<Play>>, String> {
+    cells
+        .map(|cell| match cell.trim() {
+            "" => Ok(None),
+            code => code.parse().map(Some).map_err(|e: &str| e.to_string()),
+        })
+        .collect()
+}
+
+/// A chart that's being read in, which might not have every cell filled in yet.
+struct PartialChart {
+    hard: [[Option<Play>; UPCARDS]; 18],
+    soft: [[Option<Play>; UPCARDS]; 10],
+    pairs: [[Option<Play>; UPCARDS]; 10],
+}
+
+impl PartialChart {
+    fn new() -> PartialChart {
+        PartialChart {
+            hard: [[None; UPCARDS]; 18],
+            soft: [[None; UPCARDS]; 10],
+            pairs: [[None; UPCARDS]; 10],
+        }
+    }
+
+    /// Fills in the rows for the given hands, with the plays for each upcard in `upcards`
+    /// (2 through 11 for an ace). Fails if any of the cells were already filled in.
+    fn fill(
+        &mut self,
+        hands: RowHands,
+        upcards: &[u32],
+        plays: &[Option<Play>],
+    ) -> Result<(), String> {
+        if plays.len() > upcards.len() {
+            return Err(format!(
+                "{} has more plays than there are upcards",
+                hands.describe(*hands.totals().start())
+            ));
+        }
+
+        if !matches!(hands, RowHands::Pair(_)) {
+            if let Some((&upcard, _)) = upcards
+                .iter()
+                .zip(plays)
+                .find(|(_, play)| play.is_some_and(|play| play.splits()))
+            {
+                return Err(format!(
+                    "{} against {} says to split, but only pairs can be split",
+                    hands.describe(*hands.totals().start()),
+                    upcard_label(upcard)
+                ));
+            }
+        }
+
+        let (rows, first): (&mut [[Option<Play>; UPCARDS]], u32) = match hands {
+            RowHands::Hard(..) => (&mut self.hard, LOWEST_HARD_TOTAL),
+            RowHands::Soft(..) => (&mut self.soft, LOWEST_SOFT_TOTAL),
+            RowHands::Pair(_) => (&mut self.pairs, 2),
+        };
+        for hand in hands.totals() {
+            let row = &mut rows[(hand - first) as usize];
+            for (&upcard, &play) in upcards.iter().zip(plays) {
+                let cell = &mut row[column(upcard)];
+                if cell.is_some() {
+                    return Err(format!(
+                        "{} against {} is in the chart more than once",
+                        hands.describe(hand),
+                        upcard_label(upcard)
+                    ));
+                }
+                *cell = play;
+            }
+        }
+        Ok(())
+    }
+
+    /// Finishes reading the chart, making sure every cell was filled in.
+    fn finish(self) -> Result<Chart, ChartError> {
+        let mut missing = Vec::new();
+        let hard = finish_rows(&self.hard, LOWEST_HARD_TOTAL, &mut missing, |total| {
+            RowHands::Hard(total, total).describe(total)
+        });
+        let soft = finish_rows(&self.soft, LOWEST_SOFT_TOTAL, &mut missing, |total| {
+            RowHands::Soft(total, total).describe(total)
+        });
+        let pairs = finish_rows(&self.pairs, 2, &mut missing, |value| {
+            RowHands::Pair(value).describe(value)
+        });
+
+        if !missing.is_empty() {
+            return Err(ChartError::MissingCells(missing));
+        }
+        Ok(Chart::new(hard, soft, pairs))
+    }
+}
+
+/// Turns rows that might have missing cells into finished rows, noting down every missing
+/// cell. Missing cells are left as hits in the finished rows.
+fn finish_rows<const N: usize>(
+    rows: &[[Option<Play>; UPCARDS]; N],
+    first: u32,
+    missing: &mut Vec<String>,
+    describe: impl Fn(u32) -> String,
+) -> [Row; N] {
+    let mut finished = [[Play::Hit; UPCARDS]; N];
+    for ((hand, row), finished_row) in (first..).zip(rows).zip(finished.iter_mut()) {
+        for ((upcard, cell), finished_cell) in (2..).zip(row).zip(finished_row.iter_mut()) {
+            match cell {
+                Some(play) => *finished_cell = *play,
+                None => missing.push(format!(
+                    "{} against {}",
+                    describe(hand),
+                    upcard_label(upcard)
+                )),
+            }
+        }
+    }
+    finished
+}
+
+/// The rows of a TOML chart, keyed by the hands they're for.
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TomlChart {
+    #[serde(default)]
+    hard: BTreeMap<String, String>,
+    #[serde(default)]
+    soft: BTreeMap<String, String>,
+    #[serde(default)]
+    pairs: BTreeMap<String, String>,
+}
+
+/// Parses the label of a row in one of the tables of a TOML chart.
+#[cfg(feature = "serde")]
+type LabelParser = fn(&str) -> Result<RowHands, String>;
+
+impl Chart {
+    /// Loads a strategy chart from a file. Files ending in `.toml` are read as TOML and
+    /// anything else as CSV.
+    pub fn from_file(path: &Path) -> Result<Chart, ChartError> {
+        let contents = fs::read_to_string(path).map_err(ChartError::Io)?;
+        let is_toml = path
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("toml"));
+
+        if is_toml {
+            #[cfg(feature = "serde")]
+            return Chart::from_toml(&contents);
+            #[cfg(not(feature = "serde"))]
+            return Err(ChartError::Invalid(
+                "TOML charts need the serde feature".to_string(),
+            ));
+        }
+        Chart::from_csv(&contents)
+    }
+
+    /// Reads a strategy chart written as CSV.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::blackjack::strategy::{Chart, Play};
+    ///
+    /// // A chart that never busts: stand on 12 or more, and split nothing.
+    /// let chart = Chart::from_csv(
+    ///     "hand, 2, 3, 4, 5, 6, 7, 8, 9, T, A
+    ///      H4-11, H, H, H, H, H, H, H, H, H, H
+    ///      H12+,  S, S, S, S, S, S, S, S, S, S
+    ///      S12-17,H, H, H, H, H, H, H, H, H, H
+    ///      S18+,  S, S, S, S, S, S, S, S, S, S
+    ///      22,  H, H, H, H, H, H, H, H, H, H
+    ///      33,  H, H, H, H, H, H, H, H, H, H
+    ///      44,  H, H, H, H, H, H, H, H, H, H
+    ///      55,  H, H, H, H, H, H, H, H, H, H
+    ///      66,  S, S, S, S, S, S, S, S, S, S
+    ///      77,  S, S, S, S, S, S, S, S, S, S
+    ///      88,  S, S, S, S, S, S, S, S, S, S
+    ///      99,  S, S, S, S, S, S, S, S, S, S
+    ///      TT,  S, S, S, S, S, S, S, S, S, S
+    ///      AA,  H, H, H, H, H, H, H, H, H, H",
+    /// )
+    /// .unwrap();
+    /// assert_eq!(Play::Stand, chart.hard(16, 10));
+    /// ```
+    pub fn from_csv(contents: &str) -> Result<Chart, ChartError> {
+        let invalid = |line: usize, message: String| {
+            ChartError::Invalid(format!("line {}: {}", line + 1, message))
+        };
+        let mut lines = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'));
+
+        let (header_line, header) = lines
+            .next()
+            .ok_or_else(|| ChartError::Invalid("the chart is empty".to_string()))?;
+        let upcards = header
+            .split(',')
+            .skip(1)
+            .map(card_value)
+            .collect::<Result<Vec<u32>, String>>()
+            .map_err(|e| invalid(header_line, e))?;
+        for upcard in 2..=11 {
+            if upcards.iter().filter(|&&other| other == upcard).count() != 1 {
+                return Err(invalid(
+                    header_line,
+                    format!(
+                        "the upcards need exactly one {} column",
+                        upcard_label(upcard)
+                    ),
+                ));
+            }
+        }
+
+        let mut chart = PartialChart::new();
+        for (number, line) in lines {
+            let mut cells = line.split(',');
+            let label = cells.next().unwrap_or_default();
+            let hands = RowHands::parse(label).map_err(|e| invalid(number, e))?;
+            let plays = parse_plays(cells).map_err(|e| invalid(number, e))?;
+            chart
+                .fill(hands, &upcards, &plays)
+                .map_err(|e| invalid(number, e))?;
+        }
+        chart.finish()
+    }
+
+    /// Reads a strategy chart written as TOML.
+    #[cfg(feature = "serde")]
+    pub fn from_toml(contents: &str) -> Result<Chart, ChartError> {
+        let rows: TomlChart = toml::from_str(contents).map_err(ChartError::Toml)?;
+        let upcards: Vec<u32> = (2..=11).collect();
+
+        let mut chart = PartialChart::new();
+        let sections: [(&BTreeMap<String, String>, LabelParser); 3] = [
+            (&rows.hard, RowHands::parse_hard),
+            (&rows.soft, RowHands::parse_soft),
+            (&rows.pairs, RowHands::parse_pair),
+        ];
+        for (section, parse_label) in sections {
+            for (label, plays) in section {
+                let hands = parse_label(&label.to_uppercase()).map_err(ChartError::Invalid)?;
+                let plays = parse_plays(
+                    plays
+                        .split(|c: char| c == ',' || c.is_whitespace())
+                        .filter(|cell| !cell.is_empty()),
+                )
+                .map_err(ChartError::Invalid)?;
+                chart
+                    .fill(hands, &upcards, &plays)
+                    .map_err(ChartError::Invalid)?;
+            }
+        }
+        chart.finish()
+    }
+
+    /// Writes the chart as CSV, in the same format [`Chart::from_csv`] reads. Handy as a
+    /// starting point for a custom chart.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("hand,2,3,4,5,6,7,8,9,T,A\n");
+        let mut write_row = |label: String, row: &Row| {
+            let plays: Vec<String> = row.iter().map(|play| play.to_string()).collect();
+            csv.push_str(&format!("{},{}\n", label, plays.join(",")));
+        };
+
+        for (total, row) in (LOWEST_HARD_TOTAL..).zip(&self.hard) {
+            write_row(format!("H{}", total), row);
+        }
+        for (total, row) in (LOWEST_SOFT_TOTAL..).zip(&self.soft) {
+            write_row(format!("S{}", total), row);
+        }
+        for (value, row) in (2..).zip(&self.pairs) {
+            let card = upcard_label(value);
+            write_row(format!("{}{}", card, card), row);
+        }
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::TableRules;
+
+    #[test]
+    fn csv_round_trips_charts() {
+        for rules in [
+            TableRules::default(),
+            TableRules::vegas_strip(),
+            TableRules::european(),
+        ] {
+            let chart = Chart::basic_strategy(&rules);
+            assert_eq!(chart, Chart::from_csv(&chart.to_csv()).unwrap());
+        }
+    }
+
+    #[test]
+    fn parses_row_labels() {
+        assert_eq!(Ok(RowHands::Hard(12, 12)), RowHands::parse("12"));
+        assert_eq!(Ok(RowHands::Hard(10, 10)), RowHands::parse("10"));
+        assert_eq!(Ok(RowHands::Hard(17, 21)), RowHands::parse("h17+"));
+        assert_eq!(Ok(RowHands::Hard(4, 8)), RowHands::parse("H4-8"));
+        assert_eq!(Ok(RowHands::Soft(18, 18)), RowHands::parse("A7"));
+        assert_eq!(Ok(RowHands::Soft(19, 21)), RowHands::parse("S19+"));
+        assert_eq!(Ok(RowHands::Pair(8)), RowHands::parse("88"));
+        assert_eq!(Ok(RowHands::Pair(10)), RowHands::parse("1010"));
+        assert_eq!(Ok(RowHands::Pair(11)), RowHands::parse("AA"));
+        assert_eq!(Ok(RowHands::Pair(10)), RowHands::parse("PT"));
+
+        assert!(RowHands::parse("22+").is_err());
+        assert!(RowHands::parse("H3").is_err());
+        assert!(RowHands::parse("S11").is_err());
+        assert!(RowHands::parse("H8-4").is_err());
+    }
+
+    #[test]
+    fn columns_can_come_in_any_order() {
+        let csv = Chart::basic_strategy(&TableRules::default()).to_csv();
+        // Move the ace column to the front.
+        let reordered: String = csv
+            .lines()
+            .map(|line| {
+                let (label, cells) = line.split_once(',').unwrap();
+                let (rest, ace) = cells.rsplit_once(',').unwrap();
+                format!("{},{},{}\n", label, ace, rest)
+            })
+            .collect();
+        assert_eq!(
+            Chart::basic_strategy(&TableRules::default()),
+            Chart::from_csv(&reordered).unwrap()
+        );
+    }
+
+    #[test]
+    fn finds_every_missing_cell() {
+        let csv = Chart::basic_strategy(&TableRules::default()).to_csv();
+
+        // Drop the soft 13 row, and blank out hard 12 against a 4.
+        let mut lines: Vec<String> = csv
+            .lines()
+            .filter(|line| !line.starts_with("S13,"))
+            .map(str::to_string)
+            .collect();
+        let hard_12 = lines
+            .iter()
+            .position(|line| line.starts_with("H12,"))
+            .unwrap();
+        lines[hard_12] = "H12,H,H,,S,S,H,H,H,H,H".to_string();
+
+        match Chart::from_csv(&lines.join("\n")) {
+            Err(ChartError::MissingCells(cells)) => {
+                assert_eq!(11, cells.len());
+                assert_eq!("hard 12 against 4", cells[0]);
+                assert_eq!("soft 13 against 2", cells[1]);
+            }
+            other => panic!("expected missing cells, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_charts() {
+        let csv = Chart::basic_strategy(&TableRules::default()).to_csv();
+
+        let duplicated = format!("{}H12,H,H,H,H,H,H,H,H,H,H\n", csv);
+        assert!(matches!(
+            Chart::from_csv(&duplicated),
+            Err(ChartError::Invalid(_))
+        ));
+
+        let bad_play = csv.replace("H12,H,H,S", "H12,H,H,X");
+        assert!(matches!(
+            Chart::from_csv(&bad_play),
+            Err(ChartError::Invalid(_))
+        ));
+
+        // Only pairs can be split.
+        let split_hard = csv.replace("H12,H,H,S", "H12,P,H,S");
+        let split_soft: Vec<String> = csv
+            .lines()
+            .map(|line| match line.strip_prefix("S18,") {
+                Some(plays) => format!("S18,Rp,{}", plays.split_once(',').unwrap().1),
+                None => line.to_string(),
+            })
+            .collect();
+        for bad_split in [split_hard, split_soft.join("\n")] {
+            match Chart::from_csv(&bad_split) {
+                Err(ChartError::Invalid(message)) => {
+                    assert!(message.contains("only pairs can be split"), "{}", message)
+                }
+                other => panic!("splitting a total should be invalid, got {:?}", other),
+            }
+        }
+
+        let missing_column = csv.replacen("hand,2,3,4,5,6,7,8,9,T,A", "hand,2,3,4,5,6,7,8,9,T", 1);
+        assert!(matches!(
+            Chart::from_csv(&missing_column),
+            Err(ChartError::Invalid(_))
+        ));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn reads_toml_charts() {
+        let chart = Chart::from_toml(
+            r#"
+            [hard]
+            "4-11" = "H H H H H H H H H H"
+            "12+" = "S S S S S S S S S S"
+
+            [soft]
+            "12-17" = "H H H H H H H H H H"
+            "18+" = "S S S S S S S S S S"
+
+            [pairs]
+            "2" = "H H H H H H H H H H"
+            "3" = "H H H H H H H H H H"
+            "4" = "H H H H H H H H H H"
+            "5" = "Dh,Dh,Dh,Dh,Dh,Dh,Dh,Dh,H,H"
+            "6" = "S S S S S S S S S S"
+            "7" = "S S S S S S S S S S"
+            "88" = "P P P P P P P P P P"
+            "9" = "S S S S S S S S S S"
+            "T" = "S S S S S S S S S S"
+            "A" = "P P P P P P P P P P"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(Play::Stand, chart.hard(12, 10));
+        assert_eq!(Play::Split, chart.pair(8, 11));
+        assert_eq!(Play::DoubleOrHit, chart.pair(5, 9));
+
+        let missing_pairs = Chart::from_toml("[hard]\n\"4+\" = \"H H H H H H H H H H\"");
+        assert!(matches!(missing_pairs, Err(ChartError::MissingCells(_))));
+        assert!(matches!(
+            Chart::from_toml("[hard]\n\"4+\" = \"H H H\"\n[splits]"),
+            Err(ChartError::Toml(_))
+        ));
+    }
+}
@@ -0,0 +1,411 @@
+//! Index plays: where card counters play a hand differently from the chart because of the
+//! count. Each deviation says to make a play once the true count reaches its index (or drops
+//! below it), like standing on 16 against a ten once the count is 0 or more.
+//!
+//! Deviation tables are written as CSV, with a line for each deviation: the hand (labelled
+//! the same way as the rows in chart files), the dealer's upcard, the index, and the play.
+//! Indices are written like `>=3` (at 3 or more, which can also be written `3+` or just `3`)
+//! or `<-1` (below -1). Insurance gets its own line without a play, and deviations are
+//! checked in order, so the first one that applies is the one that's played.
+//!
+//! ```text
+//! hand,      upcard, index, play
+//! insurance, A,      >=3
+//! H16,       T,      >=0,   Rs
+//! H13,       2,      <-1,   H
+//! ```
+
+use std::fs;
+use std::path::Path;
+
+use crate::blackjack::actors::Action;
+use crate::blackjack::strategy::chart_file::card_value;
+use crate::blackjack::strategy::{chart_value, Chart, ChartError, ChartHand, Play};
+use crate::cards;
+
+/// The Illustrious 18, the most valuable index plays for Hi-Lo counters in multi-deck games
+/// where the dealer stands on soft 17. Stiff hands that basic strategy might surrender use
+/// plays that still surrender where it's allowed.
+const ILLUSTRIOUS_18: &str = "
+hand,      upcard, index, play
+insurance, A,      >=3
+H16,       T,      >=0,   Rs
+H15,       T,      >=4,   Rs
+TT,        5,      >=5,   P
+TT,        6,      >=4,   P
+H10,       T,      >=4,   Dh
+H12,       3,      >=2,   S
+H12,       2,      >=3,   S
+H11,       A,      >=1,   Dh
+H9,        2,      >=1,   Dh
+H10,       A,      >=4,   Dh
+H9,        7,      >=3,   Dh
+H16,       9,      >=5,   Rs
+H13,       2,      <-1,   H
+H12,       4,      <0,    H
+H12,       5,      <-2,   H
+H12,       6,      <-1,   H
+H13,       3,      <-2,   H
+";
+
+/// The Fab 4, the index plays for late surrender that go along with the Illustrious 18.
+const FAB_4: &str = "
+hand, upcard, index, play
+H14,  T,      >=3,   Rh
+H15,  T,      <0,    H
+H15,  9,      >=2,   Rh
+H15,  A,      >=1,   Rh
+";
+
+/// Which side of its index the true count has to be on for a deviation to be played.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum IndexDirection {
+    /// The true count is at the index or above it.
+    AtOrAbove,
+    /// The true count is below the index.
+    Below,
+}
+
+/// A play to make instead of what the chart says, once the true count gets to an index.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Deviation {
+    /// The hand the deviation is for.
+    pub hand: ChartHand,
+    /// The dealer's upcard (2 through 11 for an ace).
+    pub upcard: u32,
+    /// The true count where the play changes.
+    pub index: i32,
+    /// Whether the deviation is played at or above the index, or below it.
+    pub direction: IndexDirection,
+    /// What to do instead of what the chart says.
+    pub play: Play,
+}
+
+impl Deviation {
+    /// Returns true if the deviation is played at the given true count.
+    pub fn applies(&self, true_count: f64) -> bool {
+        let index = f64::from(self.index);
+        match self.direction {
+            IndexDirection::AtOrAbove => true_count >= index,
+            IndexDirection::Below => true_count < index,
+        }
+    }
+}
+
+/// A set of index plays, along with the true count where insurance becomes worth taking.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct DeviationTable {
+    insurance_index: Option<i32>,
+    deviations: Vec<Deviation>,
+}
+
+impl DeviationTable {
+    /// A table without any deviations, which always plays by the chart and never takes insurance.
+    pub fn new() -> DeviationTable {
+        DeviationTable::default()
+    }
+
+    /// The Illustrious 18 index plays (including insurance at a true count of 3 or more).
+    pub fn illustrious_18() -> DeviationTable {
+        DeviationTable::from_csv(ILLUSTRIOUS_18).expect("The Illustrious 18 should be valid")
+    }
+
+    /// The Fab 4 late surrender index plays.
+    pub fn fab_4() -> DeviationTable {
+        DeviationTable::from_csv(FAB_4).expect("The Fab 4 should be valid")
+    }
+
+    /// Adds a deviation, checked after the ones already in the table.
+    pub fn with_deviation(mut self, deviation: Deviation) -> DeviationTable {
+        self.deviations.push(deviation);
+        self
+    }
+
+    /// Takes insurance once the true count is at the given index or above.
+    pub fn with_insurance_index(mut self, index: i32) -> DeviationTable {
+        self.insurance_index = Some(index);
+        self
+    }
+
+    /// Adds every deviation from another table, checked after the ones already in this one.
+    /// Keeps this table's insurance index if it has one.
+    ///
+    /// ```
+    /// use praeses_blackjack::blackjack::strategy::DeviationTable;
+    ///
+    /// // Surrender plays come first, as they're usually played.
+    /// let table = DeviationTable::fab_4().and(DeviationTable::illustrious_18());
+    /// assert_eq!(21, table.deviations().len());
+    /// assert_eq!(Some(3), table.insurance_index());
+    /// ```
+    pub fn and(mut self, other: DeviationTable) -> DeviationTable {
+        self.insurance_index = self.insurance_index.or(other.insurance_index);
+        self.deviations.extend(other.deviations);
+        self
+    }
+
+    /// Gets every deviation in the table, in the order they're checked.
+    pub fn deviations(&self) -> &[Deviation] {
+        &self.deviations
+    }
+
+    /// Gets the true count where insurance is worth taking, if it ever is.
+    pub fn insurance_index(&self) -> Option<i32> {
+        self.insurance_index
+    }
+
+    /// Returns true if insurance is worth taking at the given true count.
+    pub fn takes_insurance(&self, true_count: f64) -> bool {
+        self.insurance_index
+            .is_some_and(|index| true_count >= f64::from(index))
+    }
+
+    /// Gets the play for a hand against the dealer's upcard (2 through 11 for an ace) at the
+    /// given true count, or None if the chart's play stands.
+    pub fn play(&self, hand: ChartHand, upcard: u32, true_count: f64) -> Option<Play> {
+        self.deviations
+            .iter()
+            .find(|deviation| {
+                deviation.hand == hand
+                    && deviation.upcard == upcard
+                    && deviation.applies(true_count)
+            })
+            .map(|deviation| deviation.play)
+    }
+
+    /// Decides what to do with a hand against the dealer's upcard at the given true count,
+    /// out of the allowed actions. Plays by the chart unless one of the deviations applies.
+    pub fn decide(
+        &self,
+        chart: &Chart,
+        hand: &[cards::Card],
+        dealer_upcard: &cards::Card,
+        allowed_actions: &[Action],
+        true_count: f64,
+    ) -> Action {
+        let hand = ChartHand::of(hand, allowed_actions);
+        let upcard = chart_value(dealer_upcard);
+        let play = self
+            .play(hand, upcard, true_count)
+            .unwrap_or_else(|| chart.play(hand, upcard));
+        play.action(allowed_actions)
+    }
+
+    /// Loads a deviation table from a CSV file.
+    pub fn from_file(path: &Path) -> Result<DeviationTable, ChartError> {
+        let contents = fs::read_to_string(path).map_err(ChartError::Io)?;
+        DeviationTable::from_csv(&contents)
+    }
+
+    /// Reads a deviation table written as CSV.
+    pub fn from_csv(contents: &str) -> Result<DeviationTable, ChartError> {
+        let mut table = DeviationTable::new();
+        let lines = contents
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim().starts_with('#'));
+
+        for (number, line) in lines {
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            if cells[0].eq_ignore_ascii_case("hand") {
+                continue;
+            }
+            table
+                .read_line(&cells)
+                .map_err(|e| ChartError::Invalid(format!("line {}: {}", number + 1, e)))?;
+        }
+        Ok(table)
+    }
+
+    /// Adds the deviation (or insurance index) from a line of a CSV table.
+    fn read_line(&mut self, cells: &[&str]) -> Result<(), String> {
+        let (label, upcard, index, play) = match cells {
+            [label, upcard, index] => (label, upcard, index, None),
+            [label, upcard, index, play] if !play.is_empty() => (label, upcard, index, Some(play)),
+            _ => return Err("lines need a hand, upcard, index, and play".to_string()),
+        };
+        let upcard = card_value(upcard)?;
+        let (direction, index) = parse_index(index)?;
+
+        if label.eq_ignore_ascii_case("insurance") {
+            if upcard != 11 || direction != IndexDirection::AtOrAbove || play.is_some() {
+                return Err(
+                    "insurance is taken against an ace at or above an index, like \"insurance, A, >=3\""
+                        .to_string(),
+                );
+            }
+            self.insurance_index = Some(index);
+            return Ok(());
+        }
+
+        let play: Play = play
+            .ok_or_else(|| format!("the deviation for {} needs a play", label))?
+            .parse()
+            .map_err(|e: &str| e.to_string())?;
+        let hand = label.parse()?;
+        if play.splits() && !matches!(hand, ChartHand::Pair(_)) {
+            return Err(format!(
+                "the deviation for {} says to split, but only pairs can be split",
+                label
+            ));
+        }
+        self.deviations.push(Deviation {
+            hand,
+            upcard,
+            index,
+            direction,
+            play,
+        });
+        Ok(())
+    }
+}
+
+/// Parses an index like ">=3", "3+", "3", or "<-1".
+fn parse_index(index: &str) -> Result<(IndexDirection, i32), String> {
+    let (direction, number) = if let Some(number) = index.strip_prefix(">=") {
+        (IndexDirection::AtOrAbove, number)
+    } else if let Some(number) = index.strip_prefix('<') {
+        (IndexDirection::Below, number)
+    } else if let Some(number) = index.strip_suffix('+') {
+        (IndexDirection::AtOrAbove, number)
+    } else {
+        (IndexDirection::AtOrAbove, index)
+    };
+
+    let number = number
+        .trim()
+        .parse()
+        .map_err(|_| format!("{} isn't an index (try >=3 or <-1)", index))?;
+    Ok((direction, number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::TableRules;
+
+    /// Decides what to do with a hand (like "T6") against an upcard at a true count, when
+    /// everything is allowed except what the rules leave out.
+    fn decide(
+        table: &DeviationTable,
+        rules: &TableRules,
+        hand: &str,
+        upcard: &str,
+        true_count: f64,
+    ) -> Action {
+        let hand: cards::Hand = hand.parse::<cards::notation::ParsedHand>().unwrap().into();
+        let upcard = upcard.parse::<cards::Card>().unwrap();
+        let mut allowed_actions = vec![Action::Hit, Action::Stand, Action::DoubleDown];
+        if hand[0].rank == hand[1].rank {
+            allowed_actions.push(Action::Split);
+        }
+        if rules.surrender_rule != crate::blackjack::SurrenderRule::NoSurrender {
+            allowed_actions.push(Action::Surrender);
+        }
+        let chart = Chart::basic_strategy(rules);
+        table.decide(&chart, &hand, &upcard, &allowed_actions, true_count)
+    }
+
+    #[test]
+    fn ships_the_standard_index_plays() {
+        let illustrious_18 = DeviationTable::illustrious_18();
+        assert_eq!(17, illustrious_18.deviations().len());
+        assert_eq!(Some(3), illustrious_18.insurance_index());
+        assert!(illustrious_18.takes_insurance(3.0));
+        assert!(!illustrious_18.takes_insurance(2.9));
+
+        let fab_4 = DeviationTable::fab_4();
+        assert_eq!(4, fab_4.deviations().len());
+        assert!(!fab_4.takes_insurance(10.0));
+    }
+
+    #[test]
+    fn deviates_at_the_index() {
+        let table = DeviationTable::illustrious_18();
+        let rules = TableRules::default();
+
+        // Stand on 16 against a ten at 0 or more.
+        assert_eq!(Action::Hit, decide(&table, &rules, "T6", "Ts", -0.5));
+        assert_eq!(Action::Stand, decide(&table, &rules, "T6", "Ts", 0.0));
+        // Pairs of eights make 16 too, but they're split.
+        assert_eq!(Action::Split, decide(&table, &rules, "88", "Ts", 2.0));
+
+        // Split tens against a 6 at 4 or more.
+        assert_eq!(Action::Stand, decide(&table, &rules, "TT", "6c", 3.0));
+        assert_eq!(Action::Split, decide(&table, &rules, "TT", "6c", 4.0));
+
+        // Hit 13 against a 2 below -1.
+        assert_eq!(Action::Stand, decide(&table, &rules, "T3", "2c", -1.0));
+        assert_eq!(Action::Hit, decide(&table, &rules, "T3", "2c", -1.5));
+
+        // Without a deviation, the chart decides.
+        assert_eq!(Action::Stand, decide(&table, &rules, "T7", "As", -10.0));
+    }
+
+    #[test]
+    fn surrender_plays_come_first() {
+        let table = DeviationTable::fab_4().and(DeviationTable::illustrious_18());
+        let rules = TableRules::vegas_strip();
+
+        // Basic strategy surrenders 15 against a ten, but not below 0.
+        assert_eq!(Action::Hit, decide(&table, &rules, "T5", "Ts", -1.0));
+        assert_eq!(Action::Surrender, decide(&table, &rules, "T5", "Ts", 1.0));
+        // The Illustrious 18 says to stand at 4, but surrendering is still better.
+        assert_eq!(Action::Surrender, decide(&table, &rules, "T5", "Ts", 5.0));
+        // Surrender 14 against a ten at 3 or more.
+        assert_eq!(Action::Hit, decide(&table, &rules, "T4", "Ts", 2.0));
+        assert_eq!(Action::Surrender, decide(&table, &rules, "T4", "Ts", 3.0));
+
+        // Where surrender isn't allowed, the Illustrious 18 decides.
+        let no_surrender = TableRules::default();
+        assert_eq!(
+            Action::Stand,
+            decide(&table, &no_surrender, "T5", "Ts", 5.0)
+        );
+        assert_eq!(Action::Hit, decide(&table, &no_surrender, "T4", "Ts", 3.0));
+    }
+
+    #[test]
+    fn reads_deviation_tables() {
+        let table = DeviationTable::from_csv(
+            "# Double soft 19 against a 6 at 1 or more.
+             A8, 6, 1+, Ds
+             S18, 2, <0, H
+             insurance, A, 4",
+        )
+        .unwrap();
+        assert_eq!(2, table.deviations().len());
+        assert_eq!(
+            Deviation {
+                hand: ChartHand::Soft(19),
+                upcard: 6,
+                index: 1,
+                direction: IndexDirection::AtOrAbove,
+                play: Play::DoubleOrStand,
+            },
+            table.deviations()[0]
+        );
+        assert_eq!(IndexDirection::Below, table.deviations()[1].direction);
+        assert_eq!(Some(4), table.insurance_index());
+
+        for invalid in [
+            "H16, T, >=0",
+            "H16, T, zero, S",
+            "H16, T, >=0, X",
+            "H12-16, T, >=0, S",
+            "H16, T, >=0, P",
+            "S18, 6, >=1, Rp",
+            "insurance, T, >=3",
+            "insurance, A, <3",
+        ] {
+            assert!(
+                matches!(
+                    DeviationTable::from_csv(invalid),
+                    Err(ChartError::Invalid(_))
+                ),
+                "{} should be invalid",
+                invalid
+            );
+        }
+    }
+}
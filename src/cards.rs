@@ -1,13 +1,23 @@
 //! Logic and helpful structs relating to cards and decks of cards.
 
+pub mod compact;
+pub mod deck;
+pub mod notation;
+pub mod shoe;
+pub mod shuffle;
+
+pub use deck::DeckBuilder;
+pub use shoe::{Penetration, Shoe, ShuffleMode};
+
 use rand::seq::SliceRandom;
-use rand::thread_rng;
+use rand::{thread_rng, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
 use std::fmt;
 use strum::{EnumCount, IntoEnumIterator};
 use strum_macros::{EnumCount as EnumCountMacro, EnumIter};
 
-/// Enum describing the rank of a card.
-#[derive(EnumIter, EnumCountMacro, Copy, Clone, Debug, PartialEq)]
+/// Enum describing the rank of a card. Ranks are ordered from ace (low) up to king.
+#[derive(EnumIter, EnumCountMacro, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Rank {
     Ace,
     Two,
@@ -45,8 +55,8 @@ impl Rank {
     }
 }
 
-/// Enum describing the suit of a card.
-#[derive(EnumIter, EnumCountMacro, Copy, Clone, Debug, PartialEq)]
+/// Enum describing the suit of a card. Suits are ordered like in bridge: clubs, diamonds, hearts, spades.
+#[derive(EnumIter, EnumCountMacro, Copy, Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Suit {
     Club,
     Diamond,
@@ -66,14 +76,15 @@ impl Suit {
     }
 }
 
-/// Object describing a playing card.
-#[derive(Debug, Copy, Clone, PartialEq)]
+/// Object describing a playing card. Cards are ordered by rank, and then by suit.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub struct Card {
     pub rank: Rank,
     pub suit: Suit,
 }
 
-/// Represents the cards used in dealing and to give to players. Nobody owns it other than the game itself!
+/// A pile of cards, like a freshly opened deck. At the table, cards are dealt from a [`Shoe`].
+/// Decks other than the standard 52 cards can be put together with a [`DeckBuilder`].
 pub type Deck = Vec<Card>;
 
 /// Represents the cards that a player owns.
@@ -138,9 +149,26 @@ pub fn create_multideck(num_decks: u32) -> Vec<Card> {
 ///
 /// # Arguments
 ///
-/// * `deck: The deck to shuffle, as a list of cards.
-pub fn shuffle_deck(deck: &mut Vec<Card>) {
-    deck.shuffle(&mut thread_rng());
+/// * `deck` - The deck to shuffle, as a list of cards.
+pub fn shuffle_deck(deck: &mut [Card]) {
+    shuffle_deck_with(deck, &mut thread_rng());
+}
+
+/// Given a deck of cards, shuffles the deck using the given random number generator.
+/// With a seeded generator, the same seed always shuffles a deck the same way.
+///
+/// # Arguments
+///
+/// * `deck` - The deck to shuffle, as a list of cards.
+/// * `rng` - The random number generator the shuffle comes from.
+pub fn shuffle_deck_with<R: Rng + ?Sized>(deck: &mut [Card], rng: &mut R) {
+    deck.shuffle(rng);
+}
+
+/// Creates a random number generator that always shuffles the same way for the same seed,
+/// so games can be replayed. The algorithm is fixed, so seeds give the same shuffles on every platform.
+pub fn seeded_rng(seed: u64) -> ChaCha8Rng {
+    ChaCha8Rng::seed_from_u64(seed)
 }
 
 #[cfg(test)]
@@ -174,4 +202,48 @@ mod tests {
         shuffle_deck(&mut deck);
         assert_ne!(top_five_cards, &deck[..5]);
     }
+
+    #[test]
+    fn seeded_shuffles_are_reproducible() {
+        let mut first = create_multideck(6);
+        let mut second = create_multideck(6);
+        shuffle_deck_with(&mut first, &mut seeded_rng(42));
+        shuffle_deck_with(&mut second, &mut seeded_rng(42));
+        assert_eq!(first, second);
+
+        let mut other = create_multideck(6);
+        shuffle_deck_with(&mut other, &mut seeded_rng(43));
+        assert_ne!(first, other);
+    }
+
+    #[test]
+    fn cards_sort_by_rank_then_suit() {
+        let mut deck = create_multideck(1);
+        shuffle_deck_with(&mut deck, &mut seeded_rng(7));
+        deck.sort();
+        assert_eq!(
+            Card {
+                rank: Rank::Ace,
+                suit: Suit::Club
+            },
+            deck[0]
+        );
+        assert_eq!(
+            Card {
+                rank: Rank::Ace,
+                suit: Suit::Spade
+            },
+            deck[3]
+        );
+        assert_eq!(
+            Card {
+                rank: Rank::King,
+                suit: Suit::Spade
+            },
+            deck[STANDARD_DECK_COUNT - 1]
+        );
+
+        let unique: std::collections::HashSet<Card> = create_multideck(2).into_iter().collect();
+        assert_eq!(STANDARD_DECK_COUNT, unique.len());
+    }
 }
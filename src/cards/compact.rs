@@ -0,0 +1,231 @@
+//! Smaller ways of writing down cards, for simulations that go through millions of hands.
+//! A [`CompactCard`] fits a card in one byte, and a [`Composition`] keeps a hand (or a whole
+//! shoe) as how many cards of each rank it has, so nothing needs to be allocated as cards
+//! come and go.
+//!
+//! ```
+//! use praeses_blackjack::cards::compact::{CompactCard, Composition};
+//! use praeses_blackjack::cards::{Card, Rank, Suit};
+//!
+//! let card = Card { rank: Rank::Queen, suit: Suit::Heart };
+//! let compact = CompactCard::from(card);
+//! assert_eq!(card, Card::from(compact));
+//!
+//! let mut hand = Composition::new();
+//! hand.add(Rank::Ace);
+//! hand.add(Rank::Seven);
+//! assert_eq!(2, hand.len());
+//! assert_eq!(1, hand.count(Rank::Seven));
+//! ```
+
+use std::fmt;
+
+use strum::EnumCount;
+
+use crate::cards::{Card, Rank, Suit};
+
+/// Every rank, in the order of their encodings.
+const RANKS: [Rank; Rank::COUNT] = [
+    Rank::Ace,
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+];
+
+/// Every suit, in the order of their encodings.
+const SUITS: [Suit; Suit::COUNT] = [Suit::Club, Suit::Diamond, Suit::Heart, Suit::Spade];
+
+/// A card packed into a single byte: its rank times four, plus its suit. Every byte from 0
+/// up to (but not including) 52 is a card, and they sort the same way as the cards they stand for.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CompactCard(u8);
+
+impl CompactCard {
+    /// Gets the card a byte stands for, or None if it isn't one of the 52 encodings.
+    pub const fn from_byte(byte: u8) -> Option<CompactCard> {
+        if (byte as usize) < RANKS.len() * SUITS.len() {
+            Some(CompactCard(byte))
+        } else {
+            None
+        }
+    }
+
+    /// Gets the byte this card is stored as.
+    pub const fn to_byte(self) -> u8 {
+        self.0
+    }
+
+    pub const fn rank(self) -> Rank {
+        RANKS[(self.0 / SUITS.len() as u8) as usize]
+    }
+
+    pub const fn suit(self) -> Suit {
+        SUITS[(self.0 % SUITS.len() as u8) as usize]
+    }
+}
+
+impl From<Card> for CompactCard {
+    fn from(card: Card) -> Self {
+        CompactCard(card.rank as u8 * SUITS.len() as u8 + card.suit as u8)
+    }
+}
+
+impl From<CompactCard> for Card {
+    fn from(card: CompactCard) -> Self {
+        Card {
+            rank: card.rank(),
+            suit: card.suit(),
+        }
+    }
+}
+
+impl fmt::Display for CompactCard {
+    /// Shows the card the same way as a [`Card`].
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        Card::from(*self).fmt(f)
+    }
+}
+
+/// Some cards kept as how many there are of each rank, leaving out their suits and order.
+/// That's all that matters about a hand in blackjack (besides how many cards it has), and
+/// it's also a quick way to know what's left in a shoe.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Default)]
+pub struct Composition {
+    counts: [u16; Rank::COUNT],
+}
+
+impl Composition {
+    /// Starts out with no cards.
+    pub const fn new() -> Composition {
+        Composition {
+            counts: [0; Rank::COUNT],
+        }
+    }
+
+    /// The cards in some number of standard decks.
+    pub const fn standard_decks(num_decks: u16) -> Composition {
+        Composition {
+            counts: [num_decks * SUITS.len() as u16; Rank::COUNT],
+        }
+    }
+
+    /// Adds a card of the given rank.
+    pub fn add(&mut self, rank: Rank) {
+        self.counts[rank as usize] += 1;
+    }
+
+    /// Takes out a card of the given rank. Returns false (and changes nothing) if there
+    /// aren't any cards of that rank.
+    pub fn remove(&mut self, rank: Rank) -> bool {
+        let count = &mut self.counts[rank as usize];
+        if *count == 0 {
+            return false;
+        }
+        *count -= 1;
+        true
+    }
+
+    /// How many cards of the given rank there are.
+    pub const fn count(&self, rank: Rank) -> u16 {
+        self.counts[rank as usize]
+    }
+
+    /// How many cards there are in total.
+    pub fn len(&self) -> usize {
+        self.counts.iter().map(|&count| count as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counts.iter().all(|&count| count == 0)
+    }
+
+    /// Goes through each rank along with how many cards of it there are.
+    pub fn iter(&self) -> impl Iterator<Item = (Rank, u16)> + '_ {
+        RANKS.iter().map(|&rank| (rank, self.count(rank)))
+    }
+}
+
+impl<'a> From<&'a [Card]> for Composition {
+    fn from(cards: &'a [Card]) -> Self {
+        cards.iter().map(|card| card.rank).collect()
+    }
+}
+
+impl FromIterator<Rank> for Composition {
+    fn from_iter<I: IntoIterator<Item = Rank>>(ranks: I) -> Self {
+        let mut composition = Composition::new();
+        for rank in ranks {
+            composition.add(rank);
+        }
+        composition
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards;
+
+    #[test]
+    fn every_card_fits_in_a_byte() {
+        let encodings: Vec<u8> = cards::standard_deck()
+            .iter()
+            .map(|&card| CompactCard::from(card).to_byte())
+            .collect();
+        for (byte, &card) in encodings.iter().zip(cards::standard_deck().iter()) {
+            let compact = CompactCard::from_byte(*byte).unwrap();
+            assert_eq!(card, Card::from(compact));
+            assert_eq!(card.to_string(), compact.to_string());
+        }
+
+        let mut sorted = encodings.clone();
+        sorted.sort_unstable();
+        assert_eq!((0..52).collect::<Vec<u8>>(), sorted);
+        assert_eq!(None, CompactCard::from_byte(52));
+    }
+
+    #[test]
+    fn composition_counts_ranks() {
+        let hand = [
+            Card {
+                rank: Rank::Eight,
+                suit: Suit::Club,
+            },
+            Card {
+                rank: Rank::Eight,
+                suit: Suit::Heart,
+            },
+            Card {
+                rank: Rank::King,
+                suit: Suit::Spade,
+            },
+        ];
+        let mut composition = Composition::from(&hand[..]);
+        assert_eq!(3, composition.len());
+        assert_eq!(2, composition.count(Rank::Eight));
+        assert_eq!(0, composition.count(Rank::Ten));
+
+        assert!(composition.remove(Rank::King));
+        assert!(!composition.remove(Rank::King));
+        assert_eq!(2, composition.len());
+        assert!(!composition.is_empty());
+        assert!(Composition::new().is_empty());
+    }
+
+    #[test]
+    fn standard_decks_have_four_of_each_rank_per_deck() {
+        let shoe = Composition::standard_decks(6);
+        assert_eq!(6 * cards::STANDARD_DECK_COUNT, shoe.len());
+        assert!(shoe.iter().all(|(_, count)| count == 24));
+        assert_eq!(shoe, cards::create_multideck(6).as_slice().into());
+    }
+}
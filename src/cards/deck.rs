@@ -0,0 +1,226 @@
+//! Building decks that aren't the standard 52 cards. Some games take cards out (Spanish 21
+//! is played without the tens), and some experiments want more or fewer of a rank to see
+//! what it does to the game.
+//!
+//! ```
+//! use praeses_blackjack::cards::deck::DeckBuilder;
+//! use praeses_blackjack::cards::Rank;
+//!
+//! let spanish = DeckBuilder::spanish();
+//! assert_eq!(48, spanish.deck_size());
+//!
+//! // Six decks with every five taken out.
+//! let shoe = DeckBuilder::standard().without_rank(Rank::Five).build_decks(6);
+//! assert_eq!(6 * 48, shoe.len());
+//! ```
+
+use std::iter;
+use std::str::FromStr;
+
+use strum::IntoEnumIterator;
+
+use crate::cards::compact::Composition;
+use crate::cards::{self, Card, Deck, Rank, Suit};
+
+/// Puts together the cards in a single deck, which can then be built into as many decks as
+/// a shoe needs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeckBuilder {
+    /// One deck's worth of cards.
+    cards: Deck,
+}
+
+impl Default for DeckBuilder {
+    /// A standard deck.
+    fn default() -> Self {
+        DeckBuilder::standard()
+    }
+}
+
+impl DeckBuilder {
+    /// Starts from a standard deck, with one of each of the 52 cards.
+    pub fn standard() -> DeckBuilder {
+        DeckBuilder {
+            cards: cards::standard_deck().to_vec(),
+        }
+    }
+
+    /// A Spanish deck: a standard deck without the four tens, for 48 cards. (The jacks,
+    /// queens, and kings are still there.)
+    pub fn spanish() -> DeckBuilder {
+        DeckBuilder::standard().without_rank(Rank::Ten)
+    }
+
+    /// Starts from exactly the given cards.
+    pub fn from_cards(cards: impl IntoIterator<Item = Card>) -> DeckBuilder {
+        DeckBuilder {
+            cards: cards.into_iter().collect(),
+        }
+    }
+
+    /// Starts from the given number of cards of each rank. Suits are handed out in turn
+    /// (clubs, diamonds, hearts, spades, and around again), so four of a rank are one of each suit.
+    pub fn from_composition(composition: &Composition) -> DeckBuilder {
+        let cards = composition
+            .iter()
+            .flat_map(|(rank, count)| {
+                Suit::iter()
+                    .cycle()
+                    .take(count as usize)
+                    .map(move |suit| Card { rank, suit })
+            })
+            .collect();
+        DeckBuilder { cards }
+    }
+
+    /// Keeps only the cards that match the filter.
+    pub fn filter(mut self, keep: impl Fn(&Card) -> bool) -> DeckBuilder {
+        self.cards.retain(keep);
+        self
+    }
+
+    /// Takes out every card of the given rank.
+    pub fn without_rank(self, rank: Rank) -> DeckBuilder {
+        self.filter(|card| card.rank != rank)
+    }
+
+    /// Takes out every card of the given suit.
+    pub fn without_suit(self, suit: Suit) -> DeckBuilder {
+        self.filter(|card| card.suit != suit)
+    }
+
+    /// Puts `count` more copies of the given card in.
+    pub fn with_extra(mut self, card: Card, count: u32) -> DeckBuilder {
+        self.cards.extend(iter::repeat_n(card, count as usize));
+        self
+    }
+
+    /// How many cards are in one deck.
+    pub fn deck_size(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// How many cards of each rank are in one deck.
+    pub fn composition(&self) -> Composition {
+        Composition::from(&self.cards[..])
+    }
+
+    /// Builds a single deck.
+    pub fn build(&self) -> Deck {
+        self.cards.clone()
+    }
+
+    /// Builds the given number of decks, one after another.
+    pub fn build_decks(&self, num_decks: u32) -> Deck {
+        let mut deck = Vec::with_capacity(self.cards.len() * num_decks as usize);
+        for _ in 0..num_decks {
+            deck.extend_from_slice(&self.cards);
+        }
+        deck
+    }
+}
+
+/// The decks that can be picked by name.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DeckStyle {
+    Standard,
+    Spanish,
+}
+
+impl DeckStyle {
+    /// Gets a builder for this style of deck.
+    pub fn builder(&self) -> DeckBuilder {
+        match self {
+            Self::Standard => DeckBuilder::standard(),
+            Self::Spanish => DeckBuilder::spanish(),
+        }
+    }
+}
+
+impl FromStr for DeckStyle {
+    type Err = &'static str;
+
+    /// Parses a deck style from the names used on the commandline.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "standard" | "52" => Ok(Self::Standard),
+            "spanish" | "48" => Ok(Self::Spanish),
+            _ => Err("Invalid deck (try standard or spanish)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spanish_deck_has_no_tens() {
+        let deck = DeckBuilder::spanish().build();
+        assert_eq!(48, deck.len());
+        assert!(deck.iter().all(|card| card.rank != Rank::Ten));
+        assert_eq!(
+            4,
+            deck.iter().filter(|card| card.rank == Rank::King).count()
+        );
+    }
+
+    #[test]
+    fn builds_from_filters_and_extras() {
+        let deck = DeckBuilder::standard()
+            .without_suit(Suit::Heart)
+            .filter(|card| card.rank != Rank::Ace)
+            .with_extra(
+                Card {
+                    rank: Rank::Ace,
+                    suit: Suit::Spade,
+                },
+                2,
+            );
+        assert_eq!(38, deck.deck_size());
+        assert_eq!(2, deck.composition().count(Rank::Ace));
+        assert_eq!(3, deck.composition().count(Rank::Two));
+        assert_eq!(4 * 38, deck.build_decks(4).len());
+    }
+
+    #[test]
+    fn builds_from_a_composition() {
+        let mut composition = Composition::new();
+        for _ in 0..5 {
+            composition.add(Rank::Six);
+        }
+        composition.add(Rank::Ace);
+
+        let deck = DeckBuilder::from_composition(&composition);
+        assert_eq!(composition, deck.composition());
+        assert_eq!(
+            DeckBuilder::standard().composition(),
+            DeckBuilder::from_composition(&Composition::standard_decks(1)).composition()
+        );
+
+        // Suits go around in order, so the fifth six is a club again.
+        let sixes: Vec<Suit> = deck
+            .build()
+            .iter()
+            .filter(|card| card.rank == Rank::Six)
+            .map(|card| card.suit)
+            .collect();
+        assert_eq!(
+            vec![
+                Suit::Club,
+                Suit::Diamond,
+                Suit::Heart,
+                Suit::Spade,
+                Suit::Club
+            ],
+            sixes
+        );
+    }
+
+    #[test]
+    fn parses_deck_styles() {
+        assert_eq!(Ok(DeckStyle::Spanish), "Spanish".parse());
+        assert_eq!(Ok(DeckStyle::Standard), "52".parse());
+        assert!("pinochle".parse::<DeckStyle>().is_err());
+    }
+}
@@ -0,0 +1,377 @@
+//! Reading cards back from text, the way people write them down: "As", "10h", "K♦", or a
+//! whole hand like "Kd 5c". Ranks can be written "T" or "10", and suits as letters (in either
+//! case) or symbols. Anywhere a whole hand is written, suits can be left off when they don't
+//! matter, like "A7", and those cards are given spades.
+//!
+//! With the `serde` feature, cards are saved the same way, as short strings like "As" or "Td"
+//! (ranks as A, 2-9, T, J, Q, K and suits as c, d, h, s). That format won't change, so save
+//! files and hand histories can always be read back.
+//!
+//! ```
+//! use praeses_blackjack::cards::notation::{Matchup, ParsedHand};
+//! use praeses_blackjack::cards::{Card, Rank, Suit};
+//!
+//! let card: Card = "Td".parse().unwrap();
+//! assert_eq!(Card { rank: Rank::Ten, suit: Suit::Diamond }, card);
+//!
+//! let hand: ParsedHand = "Kd 5c".parse().unwrap();
+//! assert_eq!(2, hand.0.len());
+//!
+//! let matchup: Matchup = "A7 vs 6".parse().unwrap();
+//! assert_eq!(Rank::Six, matchup.dealer_upcard.rank);
+//! ```
+
+use std::fmt;
+use std::str::FromStr;
+
+#[cfg(feature = "serde")]
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::cards::{Card, Hand, Rank, Suit};
+
+impl FromStr for Rank {
+    type Err = &'static str;
+
+    /// Parses a rank from its abbreviation, like "A", "7", "T" or "10".
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_uppercase()[..] {
+            "A" => Ok(Self::Ace),
+            "2" => Ok(Self::Two),
+            "3" => Ok(Self::Three),
+            "4" => Ok(Self::Four),
+            "5" => Ok(Self::Five),
+            "6" => Ok(Self::Six),
+            "7" => Ok(Self::Seven),
+            "8" => Ok(Self::Eight),
+            "9" => Ok(Self::Nine),
+            "T" | "10" => Ok(Self::Ten),
+            "J" => Ok(Self::Jack),
+            "Q" => Ok(Self::Queen),
+            "K" => Ok(Self::King),
+            _ => Err("Invalid rank (try A, 2-9, T or 10, J, Q, or K)"),
+        }
+    }
+}
+
+impl FromStr for Suit {
+    type Err = &'static str;
+
+    /// Parses a suit from its letter (like "s" or "S") or its symbol (like "♠").
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "c" | "♣" | "♧" => Ok(Self::Club),
+            "d" | "♦" | "♢" => Ok(Self::Diamond),
+            "h" | "♥" | "♡" => Ok(Self::Heart),
+            "s" | "♠" | "♤" => Ok(Self::Spade),
+            _ => Err("Invalid suit (try c, d, h, s, or a suit symbol)"),
+        }
+    }
+}
+
+impl FromStr for Card {
+    type Err = &'static str;
+
+    /// Parses a card from its rank followed by its suit, like "As", "AS", "A♠" or "10h".
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (rank, suit, rest) = parse_card(input.trim())?;
+        match (suit, rest.is_empty()) {
+            (Some(suit), true) => Ok(Card { rank, suit }),
+            (None, _) => Err("A card needs a suit, like As or 10h"),
+            (Some(_), false) => Err("Only expected one card"),
+        }
+    }
+}
+
+/// Writes a card in plain letters, like "As" or "Td". Unlike a card's own [`fmt::Display`],
+/// this always comes back the same way through parsing, without any symbols.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Notation(pub Card);
+
+impl fmt::Display for Notation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}",
+            rank_letter(self.0.rank),
+            suit_letter(self.0.suit)
+        )
+    }
+}
+
+/// A hand of cards parsed from text, like "Kd 5c", "A♠,7♦" or "A7". Cards can be separated
+/// by spaces or commas, or not at all.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedHand(pub Hand);
+
+impl FromStr for ParsedHand {
+    type Err = &'static str;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut hand = Hand::new();
+        let mut rest = input.trim_start_matches(is_separator);
+        while !rest.is_empty() {
+            let (rank, suit, after) = parse_card(rest)?;
+            hand.push(Card {
+                rank,
+                suit: suit.unwrap_or(Suit::Spade),
+            });
+            rest = after.trim_start_matches(is_separator);
+        }
+
+        if hand.is_empty() {
+            return Err("A hand needs at least one card");
+        }
+        Ok(ParsedHand(hand))
+    }
+}
+
+impl From<ParsedHand> for Hand {
+    fn from(hand: ParsedHand) -> Self {
+        hand.0
+    }
+}
+
+/// A player's hand against the dealer's upcard, written like "A7 vs 6" or "Th 6c v As".
+#[derive(Debug, Clone, PartialEq)]
+pub struct Matchup {
+    pub hand: Hand,
+    pub dealer_upcard: Card,
+}
+
+impl FromStr for Matchup {
+    type Err = &'static str;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        const INVALID: &str = "A matchup needs a hand and the dealer's upcard, like A7 vs 6";
+        // ASCII lowercasing keeps every byte where it was, so offsets line up with the input.
+        let lowercase = input.to_ascii_lowercase();
+        let (hand, upcard) = [" vs. ", " vs ", " v "]
+            .iter()
+            .find_map(|separator| {
+                lowercase
+                    .find(separator)
+                    .map(|at| (&input[..at], &input[at + separator.len()..]))
+            })
+            .ok_or(INVALID)?;
+
+        let ParsedHand(upcard) = upcard.parse()?;
+        match upcard[..] {
+            [dealer_upcard] => Ok(Matchup {
+                hand: hand.parse::<ParsedHand>()?.0,
+                dealer_upcard,
+            }),
+            _ => Err(INVALID),
+        }
+    }
+}
+
+/// Gets the single character written for a rank, with tens written as T.
+const fn rank_letter(rank: Rank) -> char {
+    match rank {
+        Rank::Ace => 'A',
+        Rank::Two => '2',
+        Rank::Three => '3',
+        Rank::Four => '4',
+        Rank::Five => '5',
+        Rank::Six => '6',
+        Rank::Seven => '7',
+        Rank::Eight => '8',
+        Rank::Nine => '9',
+        Rank::Ten => 'T',
+        Rank::Jack => 'J',
+        Rank::Queen => 'Q',
+        Rank::King => 'K',
+    }
+}
+
+/// Gets the lowercase letter written for a suit.
+const fn suit_letter(suit: Suit) -> char {
+    match suit {
+        Suit::Club => 'c',
+        Suit::Diamond => 'd',
+        Suit::Heart => 'h',
+        Suit::Spade => 's',
+    }
+}
+
+/// Returns true for the characters allowed between cards in a hand.
+fn is_separator(character: char) -> bool {
+    character.is_whitespace() || character == ','
+}
+
+/// Parses the card at the start of the input, returning its rank and suit (if it has one)
+/// along with the rest of the input.
+fn parse_card(input: &str) -> Result<(Rank, Option<Suit>, &str), &'static str> {
+    let rank_length = if input.starts_with("10") {
+        2
+    } else {
+        input.chars().next().map_or(0, char::len_utf8)
+    };
+    let rank: Rank = input[..rank_length].parse()?;
+    let rest = &input[rank_length..];
+
+    let suit_length = rest.chars().next().map_or(0, char::len_utf8);
+    match rest[..suit_length].parse::<Suit>() {
+        Ok(suit) => Ok((rank, Some(suit), &rest[suit_length..])),
+        Err(_) => Ok((rank, None, rest)),
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Rank {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&rank_letter(*self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Suit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&suit_letter(*self))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&Notation(*self))
+    }
+}
+
+/// Reads anything that can be parsed from a string, like the card types.
+#[cfg(feature = "serde")]
+fn deserialize_parsed<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr<Err = &'static str>,
+{
+    let text = String::deserialize(deserializer)?;
+    text.parse().map_err(de::Error::custom)
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Rank {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_parsed(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Suit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_parsed(deserializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize_parsed(deserializer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn card(rank: Rank, suit: Suit) -> Card {
+        Card { rank, suit }
+    }
+
+    #[test]
+    fn parses_ranks_and_suits() {
+        assert_eq!(Ok(Rank::Ten), "T".parse());
+        assert_eq!(Ok(Rank::Ten), "10".parse());
+        assert_eq!(Ok(Rank::Ace), "a".parse());
+        assert!("1".parse::<Rank>().is_err());
+        assert!("Z".parse::<Rank>().is_err());
+
+        assert_eq!(Ok(Suit::Spade), "S".parse());
+        assert_eq!(Ok(Suit::Heart), "♥".parse());
+        assert!("x".parse::<Suit>().is_err());
+    }
+
+    #[test]
+    fn parses_cards_in_any_notation() {
+        let ace_of_spades = card(Rank::Ace, Suit::Spade);
+        assert_eq!(Ok(ace_of_spades), "As".parse());
+        assert_eq!(Ok(ace_of_spades), "AS".parse());
+        assert_eq!(Ok(ace_of_spades), "A♠".parse());
+        assert_eq!(Ok(card(Rank::Ten, Suit::Heart)), "10h".parse());
+        assert_eq!(Ok(card(Rank::Ten, Suit::Heart)), "Th".parse());
+
+        assert!("A".parse::<Card>().is_err());
+        assert!("As Kd".parse::<Card>().is_err());
+        assert!("".parse::<Card>().is_err());
+    }
+
+    #[test]
+    fn cards_parse_back_from_how_they_are_shown() {
+        for card in crate::cards::standard_deck() {
+            assert_eq!(Ok(card), card.to_string().parse());
+        }
+    }
+
+    #[test]
+    fn parses_hands() {
+        assert_eq!(
+            Ok(ParsedHand(vec![
+                card(Rank::King, Suit::Diamond),
+                card(Rank::Five, Suit::Club)
+            ])),
+            "Kd 5c".parse()
+        );
+        assert_eq!(
+            Ok(ParsedHand(vec![
+                card(Rank::Ace, Suit::Spade),
+                card(Rank::Ten, Suit::Spade),
+                card(Rank::Seven, Suit::Diamond)
+            ])),
+            "A10, 7♦".parse()
+        );
+        assert!("".parse::<ParsedHand>().is_err());
+        assert!("A7x".parse::<ParsedHand>().is_err());
+    }
+
+    #[test]
+    fn parses_matchups() {
+        assert_eq!(
+            Ok(Matchup {
+                hand: vec![card(Rank::Ace, Suit::Spade), card(Rank::Seven, Suit::Spade)],
+                dealer_upcard: card(Rank::Six, Suit::Spade),
+            }),
+            "A7 vs 6".parse()
+        );
+        let matchup: Matchup = "Th 6c V As".parse().unwrap();
+        assert_eq!(card(Rank::Ace, Suit::Spade), matchup.dealer_upcard);
+
+        assert!("A7".parse::<Matchup>().is_err());
+        assert!("A7 vs 6 5".parse::<Matchup>().is_err());
+        assert!("ȺȺ vs 6".parse::<Matchup>().is_err());
+        assert!("A7 VS ȺȺ".parse::<Matchup>().is_err());
+    }
+
+    #[test]
+    fn notation_round_trips_every_card() {
+        for card in crate::cards::standard_deck() {
+            let written = Notation(card).to_string();
+            assert_eq!(2, written.len());
+            assert_eq!(Ok(card), written.parse());
+        }
+        assert_eq!("Td", Notation(card(Rank::Ten, Suit::Diamond)).to_string());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn cards_serialize_as_notation() {
+        let hand = vec![card(Rank::Ace, Suit::Spade), card(Rank::Ten, Suit::Diamond)];
+        let json = serde_json::to_string(&hand).unwrap();
+        assert_eq!(r#"["As","Td"]"#, json);
+        assert_eq!(hand, serde_json::from_str::<Hand>(&json).unwrap());
+
+        assert_eq!(r#""T""#, serde_json::to_string(&Rank::Ten).unwrap());
+        assert_eq!(r#""h""#, serde_json::to_string(&Suit::Heart).unwrap());
+        assert_eq!(Rank::Ten, serde_json::from_str(r#""10""#).unwrap());
+        assert!(serde_json::from_str::<Card>(r#""Ax""#).is_err());
+    }
+}
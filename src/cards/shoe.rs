@@ -0,0 +1,632 @@
+//! The shoe every card at the table is dealt from. It keeps track of where each card is
+//! between shuffles: still in the shoe, burned, or played and put in the discard tray.
+
+use std::cmp;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::cards::compact::Composition;
+use crate::cards::shuffle::{Shuffler, UniformShuffle};
+use crate::cards::{self, Card, DeckBuilder, Rank};
+
+/// How deep into the shoe the dealer deals before the cut card comes out. The deeper it is,
+/// the more a card counter learns about the cards left before the reshuffle.
+/// In rules files it's written the same way it's parsed, like "75%" or "4.5 decks".
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "String", try_from = "String"))]
+pub enum Penetration {
+    /// Deal this percentage of the shoe, like 75 for three quarters of it.
+    Percent(f64),
+    /// Deal this many decks' worth of cards, like 4.5 out of a six deck shoe.
+    Decks(f64),
+}
+
+impl Default for Penetration {
+    /// Deals 80% of the shoe.
+    fn default() -> Self {
+        Penetration::Percent(80.0)
+    }
+}
+
+impl Penetration {
+    /// Gets how many cards are dealt from a shoe of `shoe_size` cards, made of decks of
+    /// `deck_size` cards each, before the cut card comes out.
+    pub fn cards_dealt(&self, shoe_size: usize, deck_size: usize) -> usize {
+        let dealt = match *self {
+            Penetration::Percent(percent) => shoe_size as f64 * percent / 100.0,
+            Penetration::Decks(decks) => decks * deck_size as f64,
+        };
+        (dealt.round().max(0.0) as usize).min(shoe_size)
+    }
+}
+
+impl FromStr for Penetration {
+    type Err = &'static str;
+
+    /// Parses a percentage (like "75%") or a number of decks (like "4.5" or "4.5 decks").
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        const INVALID: &str =
+            "Invalid penetration (try a percentage like 75% or a number of decks like 4.5)";
+        let input = input.trim().to_lowercase();
+
+        if let Some(percent) = input.strip_suffix('%') {
+            return match percent.trim().parse::<f64>() {
+                Ok(percent) if (0.0..=100.0).contains(&percent) => {
+                    Ok(Penetration::Percent(percent))
+                }
+                _ => Err(INVALID),
+            };
+        }
+
+        let decks = input
+            .strip_suffix("decks")
+            .or_else(|| input.strip_suffix("deck"))
+            .unwrap_or(&input);
+        match decks.trim().parse::<f64>() {
+            Ok(decks) if decks >= 0.0 => Ok(Penetration::Decks(decks)),
+            _ => Err(INVALID),
+        }
+    }
+}
+
+impl From<Penetration> for String {
+    fn from(penetration: Penetration) -> Self {
+        penetration.to_string()
+    }
+}
+
+impl TryFrom<String> for Penetration {
+    type Error = &'static str;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+}
+
+impl fmt::Display for Penetration {
+    /// Shows the penetration the same way it's parsed.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Penetration::Percent(percent) => write!(f, "{}%", percent),
+            Penetration::Decks(decks) => write!(f, "{} decks", decks),
+        }
+    }
+}
+
+/// How played cards make it back into the shoe.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub enum ShuffleMode {
+    /// The dealer collects the discards and shuffles the whole shoe by hand once the cut card comes out.
+    #[default]
+    CutCard,
+    /// A continuous shuffling machine (CSM): the discards go back in and get shuffled after
+    /// every round, so the shoe never runs down and counting cards doesn't help.
+    Continuous,
+}
+
+/// The seed stacked shoes shuffle with, so scenarios play out the same way every time.
+const STACKED_SEED: u64 = 0;
+
+/// A dealing shoe: the cards left to deal, the cut card that says when to reshuffle, and the
+/// cards that have already come out of it.
+#[derive(Debug, Clone)]
+pub struct Shoe {
+    /// The cards that haven't been dealt yet. The top of the shoe is the end of the `Vec`.
+    cards: Vec<Card>,
+    /// How many cards the shoe holds when every card is in it.
+    size: usize,
+    /// How many cards are in each of the decks the shoe was filled with.
+    deck_size: usize,
+    /// How many cards are left in the shoe when the cut card comes out.
+    cut_card: usize,
+    /// Where the cut card goes after every shuffle.
+    penetration: Penetration,
+    /// How many cards either side of the penetration the cut card can land.
+    cut_card_spread: usize,
+    /// Whether the shoe is shuffled by hand or by a continuous shuffling machine.
+    shuffle_mode: ShuffleMode,
+    /// How the cards get shuffled.
+    shuffler: Rc<dyn Shuffler>,
+    /// How many cards are burned after every shuffle.
+    burn_count: usize,
+    /// Cards taken off the top of the shoe without being dealt. Nobody gets to see them.
+    burned: Vec<Card>,
+    /// Cards from hands that have been played and cleared off the table.
+    discards: Vec<Card>,
+    /// Where the shoe's shuffles come from. Seeded once, so a seeded game reshuffles the same way every time.
+    rng: ChaCha8Rng,
+}
+
+impl Shoe {
+    /// Creates a freshly shuffled shoe of `num_decks` standard decks, with the cut card placed
+    /// at the default penetration. Every shuffle the shoe does comes from the given random
+    /// number generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::cards::{self, Shoe};
+    ///
+    /// let mut shoe = Shoe::new(6, &mut cards::seeded_rng(7));
+    /// assert_eq!(6 * cards::STANDARD_DECK_COUNT, shoe.len());
+    ///
+    /// let card = shoe.draw().unwrap();
+    /// shoe.discard([card]);
+    /// assert_eq!(6 * cards::STANDARD_DECK_COUNT - 1, shoe.len());
+    /// ```
+    pub fn new<R: Rng + ?Sized>(num_decks: u32, rng: &mut R) -> Shoe {
+        Shoe::with_decks(&DeckBuilder::standard(), num_decks, rng)
+    }
+
+    /// Creates a freshly shuffled shoe just like [`Shoe::new`], filled with `num_decks` of
+    /// the decks the builder makes instead of standard decks.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::cards::{self, DeckBuilder, Penetration, Shoe};
+    ///
+    /// let shoe = Shoe::with_decks(&DeckBuilder::spanish(), 6, &mut cards::seeded_rng(7))
+    ///     .with_cut_card(Penetration::Decks(5.0), 0);
+    /// assert_eq!(6 * 48, shoe.len());
+    /// assert_eq!(5 * 48, shoe.cards_before_cut_card());
+    /// ```
+    pub fn with_decks<R: Rng + ?Sized>(deck: &DeckBuilder, num_decks: u32, rng: &mut R) -> Shoe {
+        let cards = deck.build_decks(num_decks);
+        let mut shoe = Shoe {
+            size: cards.len(),
+            deck_size: deck.deck_size(),
+            cards,
+            cut_card: 0,
+            penetration: Penetration::default(),
+            cut_card_spread: 0,
+            shuffle_mode: ShuffleMode::CutCard,
+            shuffler: Rc::new(UniformShuffle),
+            burn_count: 0,
+            burned: Vec::new(),
+            discards: Vec::new(),
+            rng: ChaCha8Rng::seed_from_u64(rng.gen()),
+        };
+        shoe.shuffle();
+        shoe
+    }
+
+    /// Creates a shoe that deals exactly the given cards, from the end of the `Vec` to the start.
+    /// It isn't shuffled, burns nothing, and has no cut card, which makes it possible to play
+    /// out exact situations. If it does get reshuffled, it shuffles the same way every time.
+    pub fn stacked(cards: cards::Deck) -> Shoe {
+        Shoe {
+            size: cards.len(),
+            deck_size: cards::STANDARD_DECK_COUNT,
+            cards,
+            cut_card: 0,
+            penetration: Penetration::default(),
+            cut_card_spread: 0,
+            shuffle_mode: ShuffleMode::CutCard,
+            shuffler: Rc::new(UniformShuffle),
+            burn_count: 0,
+            burned: Vec::new(),
+            discards: Vec::new(),
+            rng: ChaCha8Rng::seed_from_u64(STACKED_SEED),
+        }
+    }
+
+    /// Places the cut card at the given penetration from now on, moving it right away.
+    /// With a spread, the cut card lands anywhere from that many cards before the penetration
+    /// to that many cards after it, picked at random every shuffle like a real cut.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::cards::{self, Penetration, Shoe};
+    ///
+    /// // Deal four and a half decks out of six before reshuffling.
+    /// let shoe = Shoe::new(6, &mut cards::seeded_rng(7)).with_cut_card(Penetration::Decks(4.5), 0);
+    /// assert_eq!(234, shoe.cards_before_cut_card());
+    /// ```
+    pub fn with_cut_card(mut self, penetration: Penetration, spread: u32) -> Shoe {
+        self.penetration = penetration;
+        self.cut_card_spread = spread as usize;
+        self.place_cut_card(self.size);
+        self
+    }
+
+    /// Burns the given number of cards off the top after every shuffle from now on, like
+    /// casinos do. Reshuffles the shoe right away, so the burn starts with a fresh shuffle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::cards::{self, Shoe};
+    ///
+    /// let shoe = Shoe::new(1, &mut cards::seeded_rng(7)).with_burn_cards(1);
+    /// assert_eq!(1, shoe.cards_burned());
+    /// assert_eq!(cards::STANDARD_DECK_COUNT - 1, shoe.len());
+    /// ```
+    pub fn with_burn_cards(mut self, count: u32) -> Shoe {
+        self.burn_count = count as usize;
+        self.shuffle();
+        self
+    }
+
+    /// Sets how played cards make it back into the shoe.
+    pub fn with_shuffle_mode(mut self, shuffle_mode: ShuffleMode) -> Shoe {
+        self.shuffle_mode = shuffle_mode;
+        self
+    }
+
+    /// Shuffles the shoe with the given shuffler from the next shuffle on. New shoes get a
+    /// perfectly random shuffle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::cards::{self, shuffle::RiffleShuffle, Shoe};
+    /// use std::rc::Rc;
+    ///
+    /// let mut shoe = Shoe::new(2, &mut cards::seeded_rng(7))
+    ///     .with_shuffler(Rc::new(RiffleShuffle { riffles: 4 }));
+    /// shoe.shuffle();
+    /// ```
+    pub fn with_shuffler(mut self, shuffler: Rc<dyn Shuffler>) -> Shoe {
+        self.shuffler = shuffler;
+        self
+    }
+
+    /// Gets how played cards make it back into the shoe.
+    pub fn shuffle_mode(&self) -> ShuffleMode {
+        self.shuffle_mode
+    }
+
+    /// Gets the number of cards left to deal before the shoe runs out.
+    pub fn len(&self) -> usize {
+        self.cards.len()
+    }
+
+    /// Returns true if there are no cards left in the shoe.
+    pub fn is_empty(&self) -> bool {
+        self.cards.is_empty()
+    }
+
+    /// Deals the top card of the shoe. If the shoe runs out in the middle of a round, the
+    /// discard tray is shuffled and put back in it, like a dealer would. Only fails if every
+    /// card is out on the table.
+    pub fn draw(&mut self) -> Result<Card, &'static str> {
+        if self.cards.is_empty() {
+            self.cards.append(&mut self.discards);
+            self.shuffler.shuffle(&mut self.cards, &mut self.rng);
+        }
+        self.cards.pop().ok_or("The shoe is out of cards.")
+    }
+
+    /// Returns true if `count` more cards can be dealt, counting the discards that would be
+    /// put back in the shoe if it runs out.
+    pub fn can_draw(&self, count: usize) -> bool {
+        self.cards.len() + self.discards.len() >= count
+    }
+
+    /// Puts played cards in the discard tray.
+    pub fn discard(&mut self, cards: impl IntoIterator<Item = Card>) {
+        self.discards.extend(cards);
+    }
+
+    /// Gets the cards in the discard tray: every card that was played and cleared off the
+    /// table since the last shuffle, in the order they were discarded.
+    pub fn discards(&self) -> &[Card] {
+        &self.discards
+    }
+
+    /// Gets how many cards have been dealt since the last shuffle, including any still out on
+    /// the table. Burned cards don't count, since nobody saw them.
+    pub fn cards_dealt(&self) -> usize {
+        self.size - self.cards.len() - self.burned.len()
+    }
+
+    /// Gets how many cards were burned after the last shuffle.
+    pub fn cards_burned(&self) -> usize {
+        self.burned.len()
+    }
+
+    /// Gets how many more cards can be dealt before the cut card comes out.
+    pub fn cards_before_cut_card(&self) -> usize {
+        self.cards.len().saturating_sub(self.cut_card)
+    }
+
+    /// Gets how many cards of each rank are left in the shoe. This is what's really in there,
+    /// which a player can't know for sure (they never see the burned cards), so a player
+    /// should only act on it when they're meant to know the whole shoe.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use praeses_blackjack::cards::{self, Rank, Shoe};
+    ///
+    /// let mut shoe = Shoe::new(2, &mut cards::seeded_rng(7));
+    /// let card = shoe.draw().unwrap();
+    /// assert_eq!(7, shoe.composition().count(card.rank));
+    /// assert_eq!(7, shoe.remaining(card.rank));
+    /// assert_eq!(103.0 / 52.0, shoe.decks_remaining());
+    /// ```
+    pub fn composition(&self) -> Composition {
+        Composition::from(&self.cards[..])
+    }
+
+    /// Gets how many cards of the given rank are left in the shoe.
+    pub fn remaining(&self, rank: Rank) -> usize {
+        self.cards.iter().filter(|card| card.rank == rank).count()
+    }
+
+    /// Gets how many decks' worth of cards are left in the shoe, which is what card counters
+    /// divide by to get their true count.
+    pub fn decks_remaining(&self) -> f64 {
+        self.cards.len() as f64 / self.deck_size as f64
+    }
+
+    /// Gets the chance that the next card dealt is of the given rank, from 0 to 1. When the
+    /// shoe is empty, that's the chance for the discards that would be shuffled back in.
+    pub fn probability_of_next(&self, rank: Rank) -> f64 {
+        let cards = self.next_cards();
+        if cards.is_empty() {
+            return 0.0;
+        }
+        let matching = cards.iter().filter(|card| card.rank == rank).count();
+        matching as f64 / cards.len() as f64
+    }
+
+    /// Returns true once the cut card has come out, meaning the shoe should be reshuffled
+    /// before the next round. Dealing doesn't stop when it comes out, so the round it comes
+    /// out in can be finished first. A continuous shuffling machine never needs it.
+    pub fn needs_shuffle(&self) -> bool {
+        self.shuffle_mode == ShuffleMode::CutCard && self.cards.len() <= self.cut_card
+    }
+
+    /// Gets the shoe ready for the next round, once the last round's cards are in the discard
+    /// tray. A continuous shuffling machine shuffles the discards back in, and otherwise the
+    /// whole shoe is reshuffled if the cut card came out. Returns true if the whole shoe was reshuffled.
+    pub fn prepare_next_round(&mut self) -> bool {
+        match self.shuffle_mode {
+            ShuffleMode::Continuous => {
+                self.cards.append(&mut self.discards);
+                self.shuffler.shuffle(&mut self.cards, &mut self.rng);
+                false
+            }
+            ShuffleMode::CutCard if self.needs_shuffle() => {
+                self.shuffle();
+                true
+            }
+            ShuffleMode::CutCard => false,
+        }
+    }
+
+    /// Gathers every card that isn't out on the table back into the shoe (the burned cards
+    /// and the discard tray), shuffles it, and burns cards off the top.
+    pub fn shuffle(&mut self) {
+        self.cards.append(&mut self.burned);
+        self.cards.append(&mut self.discards);
+        self.shuffler.shuffle(&mut self.cards, &mut self.rng);
+        self.place_cut_card(self.size);
+
+        let burn_count = cmp::min(self.burn_count, self.cards.len());
+        let burn_from = self.cards.len() - burn_count;
+        self.burned.extend(self.cards.drain(burn_from..).rev());
+    }
+
+    /// Gets the cards the next card will be dealt from: the shoe, or the discards if the shoe
+    /// is empty (since they'd be shuffled back in).
+    fn next_cards(&self) -> &[Card] {
+        if self.cards.is_empty() {
+            &self.discards
+        } else {
+            &self.cards
+        }
+    }
+
+    /// Puts the cut card back in at the shoe's penetration, for a shoe of `shoe_size` cards.
+    fn place_cut_card(&mut self, shoe_size: usize) {
+        let mut dealt = self.penetration.cards_dealt(shoe_size, self.deck_size);
+        if self.cut_card_spread > 0 {
+            let earliest = dealt.saturating_sub(self.cut_card_spread);
+            let latest = cmp::min(dealt + self.cut_card_spread, shoe_size);
+            dealt = self.rng.gen_range(earliest..=latest);
+        }
+        self.cut_card = shoe_size - dealt;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::Suit;
+
+    fn card(rank: Rank) -> Card {
+        Card {
+            rank,
+            suit: Suit::Spade,
+        }
+    }
+
+    #[test]
+    fn stacked_shoe_deals_from_the_end() {
+        let mut shoe = Shoe::stacked(vec![card(Rank::Two), card(Rank::Ace)]);
+
+        assert_eq!(Ok(card(Rank::Ace)), shoe.draw());
+        assert_eq!(Ok(card(Rank::Two)), shoe.draw());
+        assert!(shoe.is_empty());
+        assert!(shoe.draw().is_err());
+    }
+
+    #[test]
+    fn empty_shoe_refills_from_the_discards() {
+        let mut shoe = Shoe::stacked(vec![card(Rank::Ace)]);
+        let ace = shoe.draw().unwrap();
+        assert!(!shoe.can_draw(1));
+
+        shoe.discard([ace]);
+        assert!(shoe.can_draw(1));
+        assert_eq!(Ok(ace), shoe.draw());
+    }
+
+    #[test]
+    fn cut_card_comes_out_at_the_penetration() {
+        let mut shoe =
+            Shoe::new(1, &mut cards::seeded_rng(1)).with_cut_card(Penetration::Percent(75.0), 0);
+        let mut dealt = Vec::new();
+        while !shoe.needs_shuffle() {
+            dealt.push(shoe.draw().unwrap());
+        }
+        assert_eq!(39, dealt.len());
+
+        // Cards keep coming after the cut card, so the round can be finished.
+        dealt.push(shoe.draw().unwrap());
+
+        shoe.discard(dealt);
+        shoe.shuffle();
+        assert_eq!(cards::STANDARD_DECK_COUNT, shoe.len());
+        assert!(!shoe.needs_shuffle());
+    }
+
+    #[test]
+    fn cut_card_spread_stays_around_the_penetration() {
+        let mut shoe =
+            Shoe::new(6, &mut cards::seeded_rng(3)).with_cut_card(Penetration::Decks(5.0), 10);
+        let mut placements = Vec::new();
+        for _ in 0..50 {
+            shoe.shuffle();
+            placements.push(shoe.cards_before_cut_card());
+        }
+
+        assert!(placements.iter().all(|&dealt| (250..=270).contains(&dealt)));
+        assert!(placements.iter().any(|&dealt| dealt != placements[0]));
+    }
+
+    #[test]
+    fn burns_after_every_shuffle_and_tracks_discards() {
+        let mut shoe = Shoe::new(1, &mut cards::seeded_rng(4)).with_burn_cards(2);
+        assert_eq!(2, shoe.cards_burned());
+        assert_eq!(0, shoe.cards_dealt());
+
+        let hand = [shoe.draw().unwrap(), shoe.draw().unwrap()];
+        shoe.draw().unwrap();
+        shoe.discard(hand);
+        assert_eq!(3, shoe.cards_dealt());
+        assert_eq!(&hand, shoe.discards());
+        assert_eq!(cards::STANDARD_DECK_COUNT - 5, shoe.len());
+
+        // Only the cards still out on the table stay out of the shuffle.
+        shoe.shuffle();
+        assert_eq!(2, shoe.cards_burned());
+        assert!(shoe.discards().is_empty());
+        assert_eq!(1, shoe.cards_dealt());
+        assert_eq!(cards::STANDARD_DECK_COUNT - 3, shoe.len());
+    }
+
+    #[test]
+    fn continuous_shuffler_takes_discards_back_every_round() {
+        let mut shoe =
+            Shoe::new(1, &mut cards::seeded_rng(6)).with_shuffle_mode(ShuffleMode::Continuous);
+        for _ in 0..100 {
+            let hand = [shoe.draw().unwrap(), shoe.draw().unwrap()];
+            shoe.discard(hand);
+            assert!(!shoe.needs_shuffle());
+            assert!(!shoe.prepare_next_round());
+            assert_eq!(cards::STANDARD_DECK_COUNT, shoe.len());
+        }
+
+        let mut shoe = Shoe::new(1, &mut cards::seeded_rng(6));
+        while !shoe.needs_shuffle() {
+            let card = shoe.draw().unwrap();
+            shoe.discard([card]);
+            assert_eq!(shoe.needs_shuffle(), shoe.clone().prepare_next_round());
+        }
+    }
+
+    #[test]
+    fn parses_penetration() {
+        assert_eq!(Ok(Penetration::Percent(75.0)), "75%".parse());
+        assert_eq!(Ok(Penetration::Decks(4.5)), "4.5".parse());
+        assert_eq!(Ok(Penetration::Decks(5.0)), " 5 decks".parse());
+        assert_eq!(Ok(Penetration::Decks(1.0)), "1 deck".parse());
+        assert!("120%".parse::<Penetration>().is_err());
+        assert!("deep".parse::<Penetration>().is_err());
+
+        assert_eq!(234, Penetration::Percent(75.0).cards_dealt(312, 52));
+        assert_eq!(52, Penetration::Decks(2.0).cards_dealt(52, 52));
+        assert_eq!(96, Penetration::Decks(2.0).cards_dealt(288, 48));
+    }
+
+    #[test]
+    fn reports_what_is_left_in_the_shoe() {
+        let mut shoe = Shoe::stacked(vec![
+            card(Rank::Five),
+            card(Rank::King),
+            card(Rank::Five),
+            card(Rank::Ace),
+        ]);
+        assert_eq!(2, shoe.remaining(Rank::Five));
+        assert_eq!(0.5, shoe.probability_of_next(Rank::Five));
+        assert_eq!(0.0, shoe.probability_of_next(Rank::Ten));
+        assert_eq!(4.0 / 52.0, shoe.decks_remaining());
+
+        let ace = shoe.draw().unwrap();
+        assert_eq!(0, shoe.remaining(Rank::Ace));
+        assert_eq!(1.0 / 3.0, shoe.probability_of_next(Rank::King));
+
+        // Once the shoe is empty, the next card comes from the discards.
+        for _ in 0..3 {
+            shoe.draw().unwrap();
+        }
+        shoe.discard([ace]);
+        assert_eq!(1.0, shoe.probability_of_next(Rank::Ace));
+        assert!(shoe.composition().is_empty());
+    }
+
+    #[test]
+    fn composition_matches_the_cards_left() {
+        let mut shoe = Shoe::new(6, &mut cards::seeded_rng(20)).with_burn_cards(1);
+        let mut seen = Composition::new();
+        for _ in 0..100 {
+            seen.add(shoe.draw().unwrap().rank);
+        }
+
+        // Everything in the shoe, plus what's been seen, plus the burned card makes six decks.
+        let left = shoe.composition();
+        assert_eq!(211, left.len());
+        for (rank, count) in left.iter() {
+            let burned = shoe.burned.iter().filter(|card| card.rank == rank).count() as u16;
+            assert_eq!(24, count + seen.count(rank) + burned);
+        }
+    }
+
+    #[test]
+    fn stacked_shoes_reshuffle_the_same_way() {
+        let deck = || DeckBuilder::standard().build();
+        let mut first = Shoe::stacked(deck());
+        let mut second = Shoe::stacked(deck());
+        for shoe in [&mut first, &mut second] {
+            let dealt: Vec<Card> = (0..cards::STANDARD_DECK_COUNT)
+                .map(|_| shoe.draw().unwrap())
+                .collect();
+            shoe.discard(dealt);
+            shoe.shuffle();
+        }
+        for _ in 0..cards::STANDARD_DECK_COUNT {
+            assert_eq!(first.draw(), second.draw());
+        }
+    }
+
+    #[test]
+    fn seeded_shoes_shuffle_the_same_way() {
+        let mut first = Shoe::new(2, &mut cards::seeded_rng(5));
+        let mut second = Shoe::new(2, &mut cards::seeded_rng(5));
+        for _ in 0..cards::STANDARD_DECK_COUNT {
+            assert_eq!(first.draw(), second.draw());
+        }
+    }
+}
@@ -0,0 +1,343 @@
+//! Ways of shuffling cards. Real dealers don't shuffle perfectly--a riffle only interleaves
+//! two halves of the cards, a strip cut moves whole packets around--so the order of a shoe
+//! after a shuffle still says something about its order before it. These models make it
+//! possible to study that, alongside a perfectly uniform shuffle.
+//!
+//! Like a shoe, shufflers treat the end of the `Vec` as the top of the cards.
+
+use std::cmp;
+use std::fmt;
+use std::rc::Rc;
+use std::str::FromStr;
+
+use rand::{Rng, RngCore};
+
+use crate::cards::{self, Card};
+
+/// A way of shuffling cards.
+pub trait Shuffler: fmt::Debug {
+    /// Shuffles the cards, with any randomness coming from the given random number generator.
+    fn shuffle(&self, cards: &mut Vec<Card>, rng: &mut dyn RngCore);
+}
+
+/// A perfectly random shuffle, where every order of the cards is equally likely. It's the
+/// best a dealer could hope for from washing the cards (smearing them around the table).
+#[derive(Debug, Copy, Clone, Default)]
+pub struct UniformShuffle;
+
+impl Shuffler for UniformShuffle {
+    fn shuffle(&self, cards: &mut Vec<Card>, rng: &mut dyn RngCore) {
+        cards::shuffle_deck_with(cards, rng);
+    }
+}
+
+/// Riffle shuffles, following the Gilbert-Shannon-Reeds model: the cards are cut about in
+/// half (binomially), and cards drop from each half with a chance proportional to how many
+/// are left in it. It takes about seven of them to mix a single deck well.
+#[derive(Debug, Copy, Clone)]
+pub struct RiffleShuffle {
+    /// How many riffles to do in a row.
+    pub riffles: u32,
+}
+
+impl Default for RiffleShuffle {
+    /// Seven riffles.
+    fn default() -> Self {
+        RiffleShuffle { riffles: 7 }
+    }
+}
+
+impl Shuffler for RiffleShuffle {
+    fn shuffle(&self, cards: &mut Vec<Card>, rng: &mut dyn RngCore) {
+        for _ in 0..self.riffles {
+            let (bottom, top) = cut_in_half(cards, rng);
+            let (mut from_bottom, mut from_top) = (0, 0);
+            let mut riffled = Vec::with_capacity(cards.len());
+            while from_bottom < bottom.len() || from_top < top.len() {
+                let bottom_left = bottom.len() - from_bottom;
+                let top_left = top.len() - from_top;
+                if rng.gen_range(0..bottom_left + top_left) < bottom_left {
+                    riffled.push(bottom[from_bottom]);
+                    from_bottom += 1;
+                } else {
+                    riffled.push(top[from_top]);
+                    from_top += 1;
+                }
+            }
+            *cards = riffled;
+        }
+    }
+}
+
+/// A strip cut: packets of a few cards are pulled off the top one after another and piled up,
+/// which turns the order of the packets upside down while keeping the order inside each one.
+#[derive(Debug, Copy, Clone)]
+pub struct StripCut {
+    /// About how many packets the cards are stripped into.
+    pub strips: u32,
+}
+
+impl Default for StripCut {
+    /// Strips into about seven packets.
+    fn default() -> Self {
+        StripCut { strips: 7 }
+    }
+}
+
+impl Shuffler for StripCut {
+    fn shuffle(&self, cards: &mut Vec<Card>, rng: &mut dyn RngCore) {
+        let average = cmp::max(1, cards.len() / cmp::max(1, self.strips as usize));
+        let mut pile = Vec::with_capacity(cards.len());
+        while !cards.is_empty() {
+            let packet = cmp::min(rng.gen_range(1..=2 * average - 1), cards.len());
+            pile.extend(cards.drain(cards.len() - packet..));
+        }
+        *cards = pile;
+    }
+}
+
+/// A box shuffle: the cards are cut into a few piles of about the same size, which are
+/// stacked back up in the opposite order (the top pile ends up on the bottom).
+#[derive(Debug, Copy, Clone)]
+pub struct BoxShuffle {
+    /// How many piles the cards are cut into.
+    pub boxes: u32,
+}
+
+impl Default for BoxShuffle {
+    /// Cuts into four piles.
+    fn default() -> Self {
+        BoxShuffle { boxes: 4 }
+    }
+}
+
+impl Shuffler for BoxShuffle {
+    fn shuffle(&self, cards: &mut Vec<Card>, rng: &mut dyn RngCore) {
+        let boxes = cmp::max(1, self.boxes as usize);
+        let size = cards.len() / boxes;
+        // Nobody cuts perfectly even piles, so each cut can be off by an eighth of a pile.
+        let wobble = size / 8;
+        let mut pile = Vec::with_capacity(cards.len());
+        for _ in 1..boxes {
+            let packet = cmp::min(rng.gen_range(size - wobble..=size + wobble), cards.len());
+            pile.extend(cards.drain(cards.len() - packet..));
+        }
+        pile.append(cards);
+        *cards = pile;
+    }
+}
+
+/// The way a person actually riffles: the cut is only roughly in the middle, and cards fall
+/// from each hand in clumps instead of one at a time. Each riffle is followed by a cut.
+#[derive(Debug, Copy, Clone)]
+pub struct HumanShuffle {
+    /// How many riffles to do in a row.
+    pub riffles: u32,
+    /// The chance that a hand drops another card onto a clump instead of letting the other
+    /// hand go. At 0 the cards alternate perfectly, and closer to 1 the clumps get bigger.
+    pub clumping: f64,
+}
+
+impl Default for HumanShuffle {
+    /// Four riffles, with clumps of about two cards.
+    fn default() -> Self {
+        HumanShuffle {
+            riffles: 4,
+            clumping: 0.5,
+        }
+    }
+}
+
+impl Shuffler for HumanShuffle {
+    fn shuffle(&self, cards: &mut Vec<Card>, rng: &mut dyn RngCore) {
+        let clumping = self.clumping.clamp(0.0, 0.99);
+        for _ in 0..self.riffles {
+            let (bottom, top) = cut_in_half(cards, rng);
+            let (mut from_bottom, mut from_top) = (0, 0);
+            let mut riffled = Vec::with_capacity(cards.len());
+            let mut bottom_hand = rng.gen_bool(0.5);
+            while from_bottom < bottom.len() || from_top < top.len() {
+                let mut clump = 1;
+                while rng.gen_bool(clumping) {
+                    clump += 1;
+                }
+
+                // Once one hand is empty, the rest fall from the other.
+                if from_bottom == bottom.len() {
+                    bottom_hand = false;
+                } else if from_top == top.len() {
+                    bottom_hand = true;
+                }
+                let (hand, dropped) = if bottom_hand {
+                    (bottom, &mut from_bottom)
+                } else {
+                    (top, &mut from_top)
+                };
+                let clump = cmp::min(clump, hand.len() - *dropped);
+                riffled.extend_from_slice(&hand[*dropped..*dropped + clump]);
+                *dropped += clump;
+                bottom_hand = !bottom_hand;
+            }
+
+            // Cut the riffled cards somewhere near the middle.
+            let (bottom, top) = cut_in_half(&riffled, rng);
+            *cards = [top, bottom].concat();
+        }
+    }
+}
+
+/// Cuts the cards into a bottom and top half. Each card lands in either half with even odds,
+/// so the cut is usually close to the middle.
+fn cut_in_half<'a>(cards: &'a [Card], rng: &mut dyn RngCore) -> (&'a [Card], &'a [Card]) {
+    let cut = (0..cards.len()).filter(|_| rng.gen_bool(0.5)).count();
+    cards.split_at(cut)
+}
+
+/// The shuffles that can be picked by name, each with its usual settings.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum ShuffleStyle {
+    Uniform,
+    Riffle,
+    Strip,
+    Box,
+    Human,
+}
+
+impl ShuffleStyle {
+    /// Gets a shuffler for this style.
+    pub fn shuffler(&self) -> Rc<dyn Shuffler> {
+        match self {
+            Self::Uniform => Rc::new(UniformShuffle),
+            Self::Riffle => Rc::new(RiffleShuffle::default()),
+            Self::Strip => Rc::new(StripCut::default()),
+            Self::Box => Rc::new(BoxShuffle::default()),
+            Self::Human => Rc::new(HumanShuffle::default()),
+        }
+    }
+}
+
+impl FromStr for ShuffleStyle {
+    type Err = &'static str;
+
+    /// Parses a shuffle style from the names used on the commandline.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match &input.trim().to_lowercase()[..] {
+            "uniform" | "wash" => Ok(Self::Uniform),
+            "riffle" | "gsr" => Ok(Self::Riffle),
+            "strip" => Ok(Self::Strip),
+            "box" => Ok(Self::Box),
+            "human" => Ok(Self::Human),
+            _ => Err("Invalid shuffle (try uniform, riffle, strip, box, or human)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cards::seeded_rng;
+
+    /// Shuffles a fresh deck and gets where each card ended up, as its position in the fresh deck.
+    fn shuffled_positions(shuffler: &dyn Shuffler, seed: u64) -> Vec<usize> {
+        let deck = cards::create_multideck(1);
+        let mut shuffled = deck.clone();
+        shuffler.shuffle(&mut shuffled, &mut seeded_rng(seed));
+        shuffled
+            .iter()
+            .map(|card| deck.iter().position(|other| other == card).unwrap())
+            .collect()
+    }
+
+    /// Counts the rising sequences in an order: runs of consecutive cards (from the
+    /// original order) that are still in order, possibly with other cards between them.
+    fn rising_sequences(positions: &[usize]) -> usize {
+        let mut index_of = vec![0; positions.len()];
+        for (index, &position) in positions.iter().enumerate() {
+            index_of[position] = index;
+        }
+        1 + index_of.windows(2).filter(|pair| pair[1] < pair[0]).count()
+    }
+
+    #[test]
+    fn every_shuffle_keeps_the_same_cards() {
+        for style in [
+            ShuffleStyle::Uniform,
+            ShuffleStyle::Riffle,
+            ShuffleStyle::Strip,
+            ShuffleStyle::Box,
+            ShuffleStyle::Human,
+        ] {
+            let mut positions = shuffled_positions(style.shuffler().as_ref(), 1);
+            positions.sort_unstable();
+            assert_eq!(
+                (0..cards::STANDARD_DECK_COUNT).collect::<Vec<_>>(),
+                positions
+            );
+        }
+    }
+
+    #[test]
+    fn one_riffle_leaves_two_rising_sequences() {
+        for seed in 0..20 {
+            let positions = shuffled_positions(&RiffleShuffle { riffles: 1 }, seed);
+            assert!(rising_sequences(&positions) <= 2);
+        }
+
+        // Seven riffles mix things up a lot more.
+        let positions = shuffled_positions(&RiffleShuffle::default(), 0);
+        assert!(rising_sequences(&positions) > 10);
+    }
+
+    #[test]
+    fn strip_cut_turns_packets_upside_down() {
+        let positions = shuffled_positions(&StripCut::default(), 3);
+
+        // The top card ends up in the bottom packet, and packets keep their order inside, so
+        // most cards are still right above the card they were above before.
+        let top = cards::STANDARD_DECK_COUNT - 1;
+        assert!(positions.iter().position(|&p| p == top).unwrap() < 13);
+        let still_together = positions
+            .windows(2)
+            .filter(|pair| pair[1] == pair[0] + 1)
+            .count();
+        assert!(still_together > cards::STANDARD_DECK_COUNT / 2);
+    }
+
+    #[test]
+    fn box_shuffle_moves_the_top_pile_to_the_bottom() {
+        let positions = shuffled_positions(&BoxShuffle::default(), 4);
+
+        // The bottom card is now the first card of the top pile, give or take a card.
+        assert!((38..=40).contains(&positions[0]));
+        assert_eq!(4, rising_sequences(&positions));
+    }
+
+    #[test]
+    fn human_shuffle_drops_cards_in_clumps() {
+        // Counts how often neighbors from before the shuffle are still next to each other.
+        let neighbors = |shuffler: &dyn Shuffler| {
+            (0..20)
+                .map(|seed| {
+                    shuffled_positions(shuffler, seed)
+                        .windows(2)
+                        .filter(|pair| pair[1] == pair[0] + 1)
+                        .count()
+                })
+                .sum::<usize>()
+        };
+        let one_riffle = |clumping| HumanShuffle {
+            riffles: 1,
+            clumping,
+        };
+
+        assert!(neighbors(&one_riffle(0.8)) > neighbors(&one_riffle(0.0)));
+    }
+
+    #[test]
+    fn parses_shuffle_styles() {
+        assert_eq!(Ok(ShuffleStyle::Riffle), "riffle".parse());
+        assert_eq!(Ok(ShuffleStyle::Uniform), "Wash".parse());
+        assert_eq!(Ok(ShuffleStyle::Box), " box".parse());
+        assert!("overhand".parse::<ShuffleStyle>().is_err());
+    }
+}
@@ -0,0 +1,221 @@
+//! The commandline front end: plays blackjack in the terminal, showing everything that
+//! happens in the game on stdout and letting people play by typing into stdin.
+
+pub mod human_player;
+
+pub use human_player::HumanPlayer;
+
+use std::io;
+
+use crate::blackjack::{self, actors, Event, Participant, PlayerRoundResult};
+use crate::cards;
+
+/// Shows the game in the terminal, and asks whether to keep playing after every round.
+#[derive(Default)]
+pub struct Terminal {
+    /// The dealer's cards as the table sees them (the hole card is None until it's revealed).
+    dealer_cards: Vec<Option<cards::Card>>,
+    /// Whether the players have started their turns this round. From then on, cards are shown as they're dealt.
+    turns_started: bool,
+}
+
+impl Terminal {
+    pub fn new() -> Terminal {
+        Terminal::default()
+    }
+
+    /// Shows the dealer's cards, hiding the hole card if it hasn't been revealed yet.
+    fn show_dealer_hand(&self) {
+        let shown: Vec<String> = self
+            .dealer_cards
+            .iter()
+            .map(|card| match card {
+                Some(card) => card.to_string(),
+                None => "**".to_string(),
+            })
+            .collect();
+
+        let known: Option<cards::Hand> = self.dealer_cards.iter().copied().collect();
+        match known {
+            Some(hand) if hand.len() > 1 => println!(
+                "Dealer's Cards: {}     (value: {})",
+                shown.join(", "),
+                blackjack::hand_value(&hand)
+            ),
+            _ => println!("Dealer's Cards: {}", shown.join(", ")),
+        }
+    }
+}
+
+impl blackjack::Frontend for Terminal {
+    fn handle_event(&mut self, event: &Event) {
+        match event {
+            Event::RoundStarted { .. } => {
+                self.dealer_cards.clear();
+                self.turns_started = false;
+            }
+            Event::ShoeReshuffled => println!("Reshuffling deck...\n"),
+            Event::CardsBurned { .. } => {}
+            Event::PlayerBoughtIn { player, .. } => println!(
+                "You went broke, {}! Don't worry, I'll spot you some cash.",
+                player
+            ),
+            Event::BetPlaced { player, amount } => println!("{} bets ${}.", player, amount),
+            Event::CardDealt { to, card } => {
+                if *to == Participant::Dealer {
+                    self.dealer_cards.push(*card);
+                }
+                if let (true, Some(card)) = (self.turns_started, card) {
+                    println!("NEW CARD: {}", card);
+                    if *to == Participant::Dealer {
+                        self.show_dealer_hand();
+                    }
+                }
+            }
+            Event::HoleCardRevealed { card } => {
+                if let Some(hole_card) = self.dealer_cards.iter_mut().find(|card| card.is_none()) {
+                    *hole_card = Some(*card);
+                }
+                self.show_dealer_hand();
+            }
+            Event::InsuranceOffered { .. } => {
+                println!("\nDealer shows an ace!");
+                self.show_dealer_hand();
+            }
+            Event::InsurancePlaced { player, amount } => {
+                println!("{} puts up ${} for insurance.", player, amount)
+            }
+            Event::EvenMoneyTaken { player } => println!("{} takes even money.", player),
+            Event::InsuranceSettled {
+                player,
+                amount,
+                dealer_has_natural: true,
+                money,
+            } => println!(
+                "{}: Insurance pays! You won ${}. (Total cash: ${})",
+                player,
+                amount * 3,
+                money.unwrap_or(0)
+            ),
+            Event::InsuranceSettled { player, amount, .. } => println!(
+                "{}: Dealer doesn't have blackjack, you lost your ${} insurance.",
+                player, amount
+            ),
+            Event::EarlySurrenderOffered { .. } => {
+                println!("\nDealer might have blackjack!");
+                self.show_dealer_hand();
+            }
+            Event::Blackjack {
+                who: Participant::Dealer,
+            } => println!("Dealer has blackjack!"),
+            Event::Blackjack {
+                who: Participant::Player(player),
+            } => println!("{} has blackjack!", player),
+            Event::TurnStarted { player } => {
+                self.turns_started = true;
+                println!("\n---{}'s turn!---", player);
+            }
+            Event::HandInPlay {
+                player,
+                hand_number,
+                cards,
+            } => {
+                self.show_dealer_hand();
+                match hand_number {
+                    Some(number) => print!("{}'s Cards (hand {}): ", player, number),
+                    None => print!("{}'s Cards: ", player),
+                }
+                println!("{}", describe_hand(cards));
+            }
+            Event::ActionTaken { who, action } => {
+                if *action == actors::Action::Stand {
+                    return;
+                }
+                match who {
+                    Participant::Player(player) if *action == actors::Action::Surrender => {
+                        println!("{} surrenders.", player)
+                    }
+                    _ => println!("{}!", action),
+                }
+            }
+            Event::ActionRejected { reason, .. } => println!("{}", reason),
+            Event::HandBusted {
+                who: Participant::Dealer,
+            } => println!("Dealer goes bust!"),
+            Event::HandBusted { .. } => println!("Bust!"),
+            Event::DealerTurnStarted => println!("\n---Dealer's turn!---"),
+            Event::HandSettled {
+                player,
+                hand_number,
+                result,
+                bet,
+                winnings,
+                money,
+            } => {
+                match hand_number {
+                    Some(number) => print!("{} (hand {}): {} ", player, number, result),
+                    None => print!("{}: {} ", player, result),
+                }
+                match bet {
+                    Some(bet) => println!(
+                        "{} (Total cash: ${})",
+                        describe_payout(*result, *bet, *winnings),
+                        money.unwrap_or(0)
+                    ),
+                    None => println!(),
+                }
+            }
+            // Each hand was already shown as it was settled.
+            Event::RoundSettled { .. } => {}
+        }
+    }
+
+    fn play_another_round(&mut self) -> bool {
+        println!("\nPlay another round? [Y/n]");
+
+        loop {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+
+            let input = input.trim();
+
+            match &input.to_lowercase()[..] {
+                "" | "yes" | "y" => {
+                    println!();
+                    return true;
+                }
+                "n" | "no" | "q" | "quit" | "e" | "exit" => return false,
+                _ => println!(
+                    "Sorry, what was that? (try yes, no, exit, or the first letters of any of those."
+                ),
+            }
+        }
+    }
+}
+
+/// Describes a hand's cards along with its value, like "A♠, 7♦     (value: 18)".
+fn describe_hand(hand: &[cards::Card]) -> String {
+    let shown: Vec<String> = hand.iter().map(|card| card.to_string()).collect();
+    format!(
+        "{}     (value: {})",
+        shown.join(", "),
+        blackjack::hand_value(hand)
+    )
+}
+
+/// Describes what happened to the bet on a hand once it was settled.
+fn describe_payout(result: PlayerRoundResult, bet: u32, winnings: u32) -> String {
+    match result {
+        PlayerRoundResult::Natural | PlayerRoundResult::Win | PlayerRoundResult::EvenMoney => {
+            format!("You won ${}.", winnings)
+        }
+        PlayerRoundResult::Standoff => format!("You kept your original ${} bet.", bet),
+        PlayerRoundResult::Surrender => {
+            format!("You got back ${} of your ${} bet.", winnings, bet)
+        }
+        PlayerRoundResult::Lose => format!("You lost your ${} bet.", bet),
+    }
+}
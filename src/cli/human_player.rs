@@ -0,0 +1,247 @@
+use std::io;
+
+use crate::blackjack::actors::players;
+use crate::blackjack::actors::players::Player;
+use crate::blackjack::actors::Actor;
+use crate::blackjack::{self, actors};
+use crate::cards;
+use crate::cli;
+
+/// A player controlled by a human and their input into the terminal. Their output is sent to stdout.
+pub struct HumanPlayer {
+    name: String,
+    hands: Vec<players::PlayerHand>,
+    money: Option<u32>,
+}
+
+impl actors::Actor for HumanPlayer {
+    fn hand_mut(&mut self) -> &mut cards::Hand {
+        let active = self.active_hand_index();
+        &mut self.hands[active].cards
+    }
+
+    fn hand(&self) -> &[cards::Card] {
+        self.hands[self.active_hand_index()].cards.as_slice()
+    }
+}
+
+impl players::Player for HumanPlayer {
+    fn new(buy_in: u32) -> HumanPlayer {
+        println!("Input your name (or leave blank to be Player)");
+
+        let mut input = String::new();
+
+        io::stdin()
+            .read_line(&mut input)
+            .expect("Failed to read line");
+
+        input = input.trim().to_string();
+
+        if input.is_empty() {
+            input = "Player".to_owned();
+        }
+
+        let money: Option<u32> = if buy_in > 0 { Some(buy_in) } else { None };
+
+        HumanPlayer {
+            name: input,
+            hands: vec![players::PlayerHand::default()],
+            money,
+        }
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn money_mut(&mut self) -> &mut Option<u32> {
+        &mut self.money
+    }
+
+    fn money(&self) -> Option<u32> {
+        self.money
+    }
+
+    fn hands_mut(&mut self) -> &mut Vec<players::PlayerHand> {
+        &mut self.hands
+    }
+
+    fn hands(&self) -> &[players::PlayerHand] {
+        self.hands.as_slice()
+    }
+
+    fn place_bet(&mut self, rules: &blackjack::TableRules) {
+        let funds = self.money_mut();
+        if funds.is_none() {
+            return;
+        }
+        let funds = funds.unwrap();
+
+        println!(
+            "What would you like to bet this round, {}? (Funds: ${}) ",
+            self.name(),
+            funds
+        );
+
+        loop {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+
+            let input = input.trim();
+
+            let input = input.strip_prefix('$').unwrap_or(input);
+
+            if input.is_empty() || input == "0" {
+                println!("Not betting this round.");
+                return;
+            }
+
+            match input.parse::<u32>() {
+                Ok(number) => {
+                    if number > funds {
+                        println!("You don't have that kind of cash!");
+                    } else if let Err(e) = rules.check_bet(number) {
+                        println!("{}", e);
+                    } else {
+                        *self.bet_mut() = Some(number);
+                        self.money = Some(funds - number);
+                        return;
+                    }
+                }
+                Err(_e) => println!("Didn't catch that, try again."),
+            }
+        }
+    }
+
+    fn decide_insurance(&self, max_insurance: u32) -> u32 {
+        self.show_hand();
+        println!(
+            "Would you like insurance, {}? You can put up to ${} (leave blank for none)",
+            self.name(),
+            max_insurance
+        );
+
+        loop {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+
+            let input = input.trim();
+            let input = input.strip_prefix('$').unwrap_or(input);
+
+            match &input.to_lowercase()[..] {
+                "" | "0" | "n" | "no" => return 0,
+                amount => match amount.parse::<u32>() {
+                    Ok(number) if number > max_insurance => {
+                        println!("Insurance can only be up to half your bet!")
+                    }
+                    Ok(number) => return number,
+                    Err(_e) => println!("Didn't catch that, try again."),
+                },
+            }
+        }
+    }
+
+    fn decide_even_money(&self) -> bool {
+        self.show_hand();
+        println!(
+            "You have blackjack, {}! Take even money? [y/N]",
+            self.name()
+        );
+
+        loop {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+
+            match &input.trim().to_lowercase()[..] {
+                "y" | "yes" => return true,
+                "" | "n" | "no" => return false,
+                _ => println!("Sorry, what was that? (try yes or no)"),
+            }
+        }
+    }
+
+    fn decide_early_surrender(&self, _dealer_upcard: &cards::Card) -> bool {
+        self.show_hand();
+        println!(
+            "Surrender half your bet before the dealer checks for blackjack, {}? [y/N]",
+            self.name()
+        );
+
+        loop {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+
+            match &input.trim().to_lowercase()[..] {
+                "y" | "yes" => return true,
+                "" | "n" | "no" => return false,
+                _ => println!("Sorry, what was that? (try yes or no)"),
+            }
+        }
+    }
+
+    fn decide_action(
+        &self,
+        _dealer_upcard: &cards::Card,
+        allowed_actions: &[actors::Action],
+    ) -> actors::Action {
+        println!("{}", actors::Action::action_prompt(allowed_actions));
+
+        loop {
+            let mut input = String::new();
+
+            io::stdin()
+                .read_line(&mut input)
+                .expect("Failed to read line");
+
+            match actors::Action::parse_from_string(&input, allowed_actions) {
+                Ok(action) => return action,
+                Err(e) => println!("{}, try again.", e),
+            }
+        }
+    }
+}
+
+impl HumanPlayer {
+    /// Shows the player their cards when they're asked to decide something outside of their turn.
+    fn show_hand(&self) {
+        println!(
+            "{}'s Cards: {}",
+            self.name(),
+            cli::describe_hand(self.hand())
+        );
+    }
+
+    /// Used in testing to not need person's input to create a HumanPlayer.
+    #[allow(dead_code)]
+    fn new_default() -> HumanPlayer {
+        HumanPlayer {
+            name: "Player".to_string(),
+            hands: vec![players::PlayerHand::default()],
+            money: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::blackjack::actors::tests as actor_tests;
+
+    /// Check that
+    #[test]
+    fn human_player_adds_card_to_hand() {
+        actor_tests::adds_card_to_hand(HumanPlayer::new_default());
+    }
+}
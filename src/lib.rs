@@ -4,6 +4,7 @@
 
 pub mod blackjack;
 pub mod cards;
+pub mod cli;
 
 pub use crate::blackjack::actors::dealers::Dealer;
 pub use crate::blackjack::actors::players::Player;
@@ -0,0 +1,557 @@
+//! Plays out exact rounds of blackjack from stacked decks, checking that every way a round
+//! can end settles the right way.
+
+use praeses_blackjack::blackjack::actors::dealers::StandardDealer;
+use praeses_blackjack::blackjack::actors::players::{
+    AutoPlayer, BasicStrategyPlayer, CountingPlayer, ScriptedPlayer,
+};
+use praeses_blackjack::blackjack::actors::Action;
+use praeses_blackjack::blackjack::actors::Actor;
+use praeses_blackjack::blackjack::counting::{CardCount, HiLo};
+use praeses_blackjack::blackjack::{
+    self, Event, Frontend, Participant, PlayerRoundResult, RoundOutcome, Scenario, TableRules,
+};
+use praeses_blackjack::cards::{self, Rank};
+use praeses_blackjack::Player;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+/// A front end that keeps every event from the round.
+#[derive(Default)]
+struct Recorder {
+    events: Vec<Event>,
+}
+
+impl Frontend for Recorder {
+    fn handle_event(&mut self, event: &Event) {
+        self.events.push(event.clone());
+    }
+
+    fn play_another_round(&mut self) -> bool {
+        false
+    }
+}
+
+impl Recorder {
+    /// Counts the cards dealt to the dealer (including the hole card).
+    fn dealer_cards(&self) -> usize {
+        self.events
+            .iter()
+            .filter(|event| {
+                matches!(
+                    event,
+                    Event::CardDealt {
+                        to: Participant::Dealer,
+                        ..
+                    }
+                )
+            })
+            .count()
+    }
+}
+
+/// A player that stands on everything and keeps the Hi-Lo count of what it's shown, which can
+/// be looked at once the round is over.
+struct Watcher {
+    hands: Vec<blackjack::actors::players::PlayerHand>,
+    money: Option<u32>,
+    count: Rc<RefCell<CardCount<HiLo>>>,
+}
+
+impl Actor for Watcher {
+    fn hand_mut(&mut self) -> &mut cards::Hand {
+        let active = self.active_hand_index();
+        &mut self.hands[active].cards
+    }
+
+    fn hand(&self) -> &[cards::Card] {
+        self.hands[self.active_hand_index()].cards.as_slice()
+    }
+}
+
+impl Player for Watcher {
+    fn new(_buy_in: u32) -> Watcher {
+        Watcher {
+            hands: vec![Default::default()],
+            money: None,
+            count: Rc::new(RefCell::new(CardCount::new(HiLo, 1))),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "Watcher"
+    }
+
+    fn money_mut(&mut self) -> &mut Option<u32> {
+        &mut self.money
+    }
+
+    fn money(&self) -> Option<u32> {
+        self.money
+    }
+
+    fn hands_mut(&mut self) -> &mut Vec<blackjack::actors::players::PlayerHand> {
+        &mut self.hands
+    }
+
+    fn hands(&self) -> &[blackjack::actors::players::PlayerHand] {
+        self.hands.as_slice()
+    }
+
+    fn place_bet(&mut self, _rules: &TableRules) {}
+
+    fn decide_action(&self, _dealer_upcard: &cards::Card, _allowed_actions: &[Action]) -> Action {
+        Action::Stand
+    }
+
+    fn observe(&mut self, event: &Event) {
+        self.count.borrow_mut().observe(event);
+    }
+}
+
+fn play(scenario: Scenario) -> (RoundOutcome, Recorder) {
+    let mut recorder = Recorder::default();
+    let outcome = scenario.play::<StandardDealer>(&mut recorder).unwrap();
+    (outcome, recorder)
+}
+
+fn stands(buy_in: u32) -> ScriptedPlayer {
+    ScriptedPlayer::new(buy_in)
+}
+
+#[test]
+fn dealer_blackjack_ends_round_before_players_act() {
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Nine])
+            .player_with_bet(stands(100), 10, [Rank::Ace, Rank::King])
+            .dealer(Rank::Ten, Rank::Ace)
+            .draws(&[Rank::Five]),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[0]);
+    assert_eq!(vec![PlayerRoundResult::Standoff], outcome.results[1]);
+    assert_eq!(Some(90), outcome.players[0].money());
+    assert_eq!(Some(100), outcome.players[1].money());
+
+    // Nobody took a turn, so the extra card is still in the shoe.
+    assert_eq!(1, outcome.shoe.len());
+    assert!(!recorder
+        .events
+        .iter()
+        .any(|event| matches!(event, Event::TurnStarted { .. })));
+}
+
+#[test]
+fn natural_pays_three_to_two() {
+    let (outcome, _) = play(
+        Scenario::new()
+            .player_with_bet(stands(100), 10, [Rank::Ace, Rank::Queen])
+            .dealer(Rank::Nine, Rank::Seven),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Natural], outcome.results[0]);
+    assert_eq!(Some(115), outcome.players[0].money());
+}
+
+#[test]
+fn natural_still_pays_when_dealer_makes_21() {
+    let (outcome, _) = play(
+        Scenario::new()
+            .player_with_bet(stands(100), 10, [Rank::Ace, Rank::Queen])
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Eight])
+            .dealer(Rank::Six, Rank::Ten)
+            .draws(&[Rank::Five]),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Natural], outcome.results[0]);
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[1]);
+    assert_eq!(Some(115), outcome.players[0].money());
+}
+
+#[test]
+fn insurance_pays_when_dealer_has_blackjack() {
+    // A player who always insures as much as they can.
+    struct Insurer(ScriptedPlayer);
+
+    impl blackjack::actors::Actor for Insurer {
+        fn hand_mut(&mut self) -> &mut praeses_blackjack::cards::Hand {
+            self.0.hand_mut()
+        }
+
+        fn hand(&self) -> &[praeses_blackjack::cards::Card] {
+            self.0.hand()
+        }
+    }
+
+    impl Player for Insurer {
+        fn new(buy_in: u32) -> Self {
+            Insurer(ScriptedPlayer::new(buy_in))
+        }
+
+        fn name(&self) -> &str {
+            "Insurer"
+        }
+
+        fn money_mut(&mut self) -> &mut Option<u32> {
+            self.0.money_mut()
+        }
+
+        fn money(&self) -> Option<u32> {
+            self.0.money()
+        }
+
+        fn hands_mut(&mut self) -> &mut Vec<blackjack::actors::players::PlayerHand> {
+            self.0.hands_mut()
+        }
+
+        fn hands(&self) -> &[blackjack::actors::players::PlayerHand] {
+            self.0.hands()
+        }
+
+        fn place_bet(&mut self, _rules: &TableRules) {}
+
+        fn decide_insurance(&self, max_insurance: u32) -> u32 {
+            max_insurance
+        }
+
+        fn decide_action(
+            &self,
+            dealer_upcard: &praeses_blackjack::cards::Card,
+            allowed_actions: &[Action],
+        ) -> Action {
+            self.0.decide_action(dealer_upcard, allowed_actions)
+        }
+    }
+
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(Insurer::new(100), 20, [Rank::Ten, Rank::Nine])
+            .dealer(Rank::Ace, Rank::King),
+    );
+
+    // Lost the $20 bet, but the $10 insurance paid 2:1.
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[0]);
+    assert_eq!(Some(100), outcome.players[0].money());
+    assert!(recorder.events.contains(&Event::InsuranceOffered {
+        dealer_upcard: praeses_blackjack::cards::Card {
+            rank: Rank::Ace,
+            suit: praeses_blackjack::cards::Suit::Spade,
+        }
+    }));
+}
+
+#[test]
+fn dealer_skips_turn_when_every_hand_is_decided() {
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Hit]),
+                10,
+                [Rank::Ten, Rank::Six],
+            )
+            .dealer(Rank::Six, Rank::Ten)
+            .draws(&[Rank::King, Rank::Five]),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[0]);
+    assert_eq!(Some(90), outcome.players[0].money());
+    // The dealer's 16 never drew the five.
+    assert_eq!(1, outcome.shoe.len());
+    assert_eq!(2, recorder.dealer_cards());
+    assert!(!recorder.events.contains(&Event::DealerTurnStarted));
+}
+
+#[test]
+fn late_surrender_refunds_half_without_dealer_turn() {
+    let rules = TableRules::vegas_strip();
+    let (outcome, _) = play(
+        Scenario::new()
+            .rules(rules)
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Surrender]),
+                30,
+                [Rank::Ten, Rank::Six],
+            )
+            .dealer(Rank::Ten, Rank::Seven),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Surrender], outcome.results[0]);
+    assert_eq!(Some(85), outcome.players[0].money());
+}
+
+#[test]
+fn dealer_bust_pays_standing_hands() {
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Two])
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Hit]),
+                10,
+                [Rank::Ten, Rank::Five],
+            )
+            .dealer(Rank::Six, Rank::Ten)
+            .draws(&[Rank::Nine, Rank::Eight]),
+    );
+
+    // The second player busted before the dealer did, so they still lose.
+    assert_eq!(vec![PlayerRoundResult::Win], outcome.results[0]);
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[1]);
+    assert_eq!(Some(110), outcome.players[0].money());
+    assert_eq!(Some(90), outcome.players[1].money());
+    assert!(recorder.events.contains(&Event::HandBusted {
+        who: Participant::Dealer
+    }));
+}
+
+#[test]
+fn complete_round_compares_hands() {
+    let (outcome, _) = play(
+        Scenario::new()
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Nine])
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Eight])
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Seven])
+            .dealer(Rank::Ten, Rank::Eight),
+    );
+
+    assert_eq!(
+        vec![
+            vec![PlayerRoundResult::Win],
+            vec![PlayerRoundResult::Standoff],
+            vec![PlayerRoundResult::Lose]
+        ],
+        outcome.results
+    );
+    let money: Vec<Option<u32>> = outcome
+        .players
+        .iter()
+        .map(|player| player.money())
+        .collect();
+    assert_eq!(vec![Some(110), Some(100), Some(90)], money);
+}
+
+#[test]
+fn dealer_follows_soft_17_rule() {
+    let scenario = |rules: TableRules| {
+        Scenario::new()
+            .rules(rules)
+            .player_with_bet(stands(100), 10, [Rank::Ten, Rank::Eight])
+            .dealer(Rank::Six, Rank::Ace)
+            .draws(&[Rank::Two])
+    };
+
+    // Standing on soft 17 loses to 18.
+    let (outcome, _) = play(scenario(TableRules::default()));
+    assert_eq!(vec![PlayerRoundResult::Win], outcome.results[0]);
+
+    // Hitting soft 17 catches a two for 19.
+    let (outcome, _) = play(scenario(TableRules {
+        dealer_hits_soft_17: true,
+        ..TableRules::default()
+    }));
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[0]);
+}
+
+#[test]
+fn split_hands_settle_separately() {
+    let (outcome, _) = play(
+        Scenario::new()
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Split]),
+                10,
+                [Rank::Eight, Rank::Eight],
+            )
+            .dealer(Rank::Ten, Rank::Eight)
+            .draws(&[Rank::Ten, Rank::Nine]),
+    );
+
+    // 18 pushes against the dealer's 18, and 17 loses.
+    assert_eq!(
+        vec![PlayerRoundResult::Standoff, PlayerRoundResult::Lose],
+        outcome.results[0]
+    );
+    assert_eq!(Some(90), outcome.players[0].money());
+}
+
+#[test]
+fn no_hole_card_blackjack_takes_doubled_bets() {
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .rules(TableRules::european())
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::DoubleDown]),
+                10,
+                [Rank::Six, Rank::Four],
+            )
+            .dealer_upcard(Rank::Ace)
+            .draws(&[Rank::Ten, Rank::King]),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[0]);
+    assert_eq!(Some(80), outcome.players[0].money());
+    assert!(recorder.events.contains(&Event::Blackjack {
+        who: Participant::Dealer
+    }));
+}
+
+#[test]
+fn bots_play_from_stacked_decks() {
+    // The bot stands on 13 or more against a dealer's 2 or 3.
+    let (outcome, _) = play(
+        Scenario::new()
+            .player_with_bet(AutoPlayer::new(100), 10, [Rank::Ten, Rank::Three])
+            .dealer(Rank::Two, Rank::Ten)
+            .draws(&[Rank::Ten]),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Win], outcome.results[0]);
+    assert!(outcome.shoe.is_empty());
+}
+
+#[test]
+fn basic_strategy_bot_splits_and_doubles() {
+    // Split eights against a 6, then double the 8-3 that comes of it.
+    let (outcome, _) = play(
+        Scenario::new()
+            .player_with_bet(
+                BasicStrategyPlayer::new(100),
+                10,
+                [Rank::Eight, Rank::Eight],
+            )
+            .dealer(Rank::Six, Rank::Ten)
+            .draws(&[Rank::Three, Rank::Ten, Rank::Nine, Rank::Eight]),
+    );
+
+    // 8-3 doubles into 20 and 8-10 stands at 18, while the dealer's 16 busts with an eight.
+    assert_eq!(
+        vec![PlayerRoundResult::Win, PlayerRoundResult::Win],
+        outcome.results[0]
+    );
+    assert_eq!(Some(130), outcome.players[0].money());
+}
+
+#[test]
+fn hands_stand_once_the_shoe_runs_out() {
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Hit]),
+                10,
+                [Rank::Ten, Rank::Two],
+            )
+            .dealer(Rank::Six, Rank::Ten),
+    );
+
+    // Nobody can draw, so the player's 12 loses to the dealer's 16.
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[0]);
+    assert_eq!(2, recorder.dealer_cards());
+    assert!(outcome.shoe.is_empty());
+}
+
+#[test]
+fn illegal_actions_stand_after_a_few_tries() {
+    // A player who does nothing but try to split a hand that isn't a pair.
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Split; 100]),
+                10,
+                [Rank::Ten, Rank::Nine],
+            )
+            .dealer(Rank::Ten, Rank::Eight)
+            // Enough cards left that splitting isn't ruled out by the shoe running dry.
+            .draws(&[Rank::Two, Rank::Three]),
+    );
+
+    let rejections = recorder
+        .events
+        .iter()
+        .filter(|event| matches!(event, Event::ActionRejected { .. }))
+        .count();
+    assert_eq!(3, rejections);
+    assert!(recorder.events.contains(&Event::ActionTaken {
+        who: Participant::Player("Scripted".to_string()),
+        action: Action::Stand,
+    }));
+    assert_eq!(vec![PlayerRoundResult::Win], outcome.results[0]);
+}
+
+#[test]
+fn bots_keep_the_bet_they_were_seated_with() {
+    // The counting bot would bet the table minimum on its own.
+    let (outcome, recorder) = play(
+        Scenario::new()
+            .player_with_bet(
+                CountingPlayer::<HiLo>::new(100),
+                20,
+                [Rank::Ten, Rank::Nine],
+            )
+            .dealer(Rank::Ten, Rank::Eight),
+    );
+
+    assert_eq!(vec![PlayerRoundResult::Win], outcome.results[0]);
+    assert_eq!(Some(120), outcome.players[0].money());
+    assert!(recorder.events.contains(&Event::BetPlaced {
+        player: "Hi-Lo Counter".to_string(),
+        amount: 20,
+    }));
+}
+
+#[test]
+fn players_see_every_card_on_the_table() {
+    let watcher = Watcher::new(0);
+    let count = Rc::clone(&watcher.count);
+    play(
+        Scenario::new()
+            .player(watcher, [Rank::Ten, Rank::Nine])
+            .player_with_bet(
+                ScriptedPlayer::with_actions(100, &[Action::Hit]),
+                10,
+                [Rank::Two, Rank::Three],
+            )
+            .dealer(Rank::Six, Rank::Five)
+            .draws(&[Rank::Four, Rank::Ace, Rank::King]),
+    );
+
+    // The watcher's 10-9 is -1, the other seat's 2-3-4 is +3, and the dealer's 6-5 (with the
+    // hole card turned over) and A-K come to 0.
+    assert_eq!(2.0, count.borrow().running_count());
+    // The stacked shoe only had the cards for this round in it.
+    assert_eq!(0, count.borrow().cards_remaining());
+}
+
+#[test]
+fn counting_bot_plays_by_the_count() {
+    // Seats two stand-pat players and then a counting bot with 16 against a ten, which it
+    // stands on once the true count is 0 or more.
+    let counting_round = |first: [Rank; 2], second: [Rank; 2], draws: &[Rank]| {
+        play(
+            Scenario::new()
+                .player_with_bet(stands(100), 10, first)
+                .player_with_bet(stands(100), 10, second)
+                .player_with_bet(CountingPlayer::<HiLo>::new(100), 10, [Rank::Ten, Rank::Six])
+                .dealer(Rank::Ten, Rank::Seven)
+                .draws(draws),
+        )
+        .0
+    };
+
+    // Low cards at the table push the count up, so the bot stands and loses to the dealer's 17.
+    let outcome = counting_round(
+        [Rank::Two, Rank::Three],
+        [Rank::Four, Rank::Five],
+        &[Rank::Four],
+    );
+    assert_eq!(vec![PlayerRoundResult::Lose], outcome.results[2]);
+    assert_eq!(1, outcome.shoe.len());
+
+    // High cards push it down, so the bot plays basic strategy and hits its way to 20.
+    let outcome = counting_round(
+        [Rank::Ten, Rank::King],
+        [Rank::Queen, Rank::Jack],
+        &[Rank::Four],
+    );
+    assert_eq!(vec![PlayerRoundResult::Win], outcome.results[2]);
+    assert!(outcome.shoe.is_empty());
+}